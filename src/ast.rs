@@ -1,41 +1,81 @@
+use std::fmt;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ASTNode {
     // Statements
-    Program(Vec<Box<ASTNode>>),
+    Program(Vec<ASTNode>),
     VarDeclaration {
         name: String,
         type_hint: Option<String>,
         value: Box<ASTNode>,
+        is_const: bool,
+    },
+    Destructuring {
+        names: Vec<String>, // maanau [a, b] = list / maanau {x, y} = dict
+        from_dictionary: bool, // names are looked up as keys instead of by position
+        value: Box<ASTNode>,
+        is_const: bool,
     },
+    Delete(String), // birsau x
     Assignment {
         name: String,
         value: Box<ASTNode>,
     },
+    Step {
+        name: String,
+        delta: f64, // badha x adds 1, ghata x adds -1
+    },
     IfStatement {
         condition: Box<ASTNode>,
-        then_block: Vec<Box<ASTNode>>,
-        else_block: Option<Vec<Box<ASTNode>>>,
+        then_block: Vec<ASTNode>,
+        else_block: Option<Vec<ASTNode>>,
     },
     WhileLoop {
         condition: Box<ASTNode>,
-        body: Vec<Box<ASTNode>>,
+        body: Vec<ASTNode>,
+    },
+    DoWhileLoop {
+        body: Vec<ASTNode>,
+        condition: Box<ASTNode>,
     },
     ForEachLoop {
+        index: Option<String>, // pratyek i, x ma ...: list index or dictionary key
         variable: String,
         iterable: Box<ASTNode>,
-        body: Vec<Box<ASTNode>>,
+        body: Vec<ASTNode>,
+    },
+    RepeatLoop {
+        count: Box<ASTNode>, // dohoryau 5 { ... }
+        body: Vec<ASTNode>,
+    },
+    ChhanStatement {
+        subject: Box<ASTNode>,
+        arms: Vec<(Box<ASTNode>, Vec<ASTNode>)>, // (value to match, block)
+        default: Option<Vec<ASTNode>>, // natra block
+    },
+    TryCatch {
+        try_block: Vec<ASTNode>, // kosis { ... } samat e { ... }
+        error_name: String, // bound to the error message in the catch block
+        catch_block: Vec<ASTNode>,
     },
     FunctionDeclaration {
         name: String,
-        parameters: Vec<String>,
-        body: Vec<Box<ASTNode>>,
+        parameters: Vec<(String, Option<Box<ASTNode>>)>, // (name, default value)
+        body: Vec<ASTNode>,
+        is_variadic: bool, // last parameter collects extra arguments
     },
     Return(Box<ASTNode>),
-    Print(Box<ASTNode>),
+    Throw(Box<ASTNode>), // phek "message": raise a runtime error
+    Print(Vec<ASTNode>), // bhan a, b -> values joined by spaces
+    PrintInline(Box<ASTNode>), // likha: print without a trailing newline
     Break,
     Continue,
     Import {
         filename: String,
+        alias: Option<String>, // aayaat "x.nep" naam ma alias
+    },
+    ModuleImport {
+        filename: String, // aayaat_le "file.nep" evaluates to the module's exports
     },
     
     // Expressions
@@ -50,14 +90,35 @@ pub enum ASTNode {
     },
     FunctionCall {
         name: String,
-        arguments: Vec<Box<ASTNode>>,
+        arguments: Vec<ASTNode>,
+    },
+    ExpressionCall {
+        callee: Box<ASTNode>, // e.g. m["add"](1, 2) or m.add(1, 2)
+        arguments: Vec<ASTNode>,
     },
-    ListLiteral(Vec<Box<ASTNode>>),
-    DictionaryLiteral(Vec<(String, Box<ASTNode>)>), // key-value pairs
+    MemberAccess {
+        object: Box<ASTNode>,
+        member: String, // always called: m.add(1, 2)
+    },
+    PropertyAccess {
+        object: Box<ASTNode>,
+        property: String, // no call: mylist.lambai, or a dictionary key like m.PI
+    },
+    IfExpression {
+        condition: Box<ASTNode>, // maanau x = yedi c bhane { a } natra { b }
+        then_block: Vec<ASTNode>,
+        else_block: Option<Vec<ASTNode>>,
+    },
+    ListLiteral(Vec<ASTNode>),
+    DictionaryLiteral(Vec<(Box<ASTNode>, Box<ASTNode>)>), // key-value pairs
     IndexAccess {
         object: Box<ASTNode>,
         index: Box<ASTNode>,
     },
+    SafeIndexAccess {
+        object: Box<ASTNode>, // x?[i]: null when i is missing or x can't be indexed
+        index: Box<ASTNode>,
+    },
     IndexAssignment {
         object: Box<ASTNode>,
         index: Box<ASTNode>,
@@ -67,25 +128,44 @@ pub enum ASTNode {
     Number(String),
     String(String),
     Boolean(bool),
+    Null, // value of a bare `pathau`
 }
 
 impl ASTNode {
-    pub fn new_program(statements: Vec<Box<ASTNode>>) -> Self {
+    pub fn new_program(statements: Vec<ASTNode>) -> Self {
         ASTNode::Program(statements)
     }
     
-    pub fn new_var_declaration(name: String, type_hint: Option<String>, value: Box<ASTNode>) -> Self {
-        ASTNode::VarDeclaration { name, type_hint, value }
+    pub fn new_var_declaration(
+        name: String,
+        type_hint: Option<String>,
+        value: Box<ASTNode>,
+        is_const: bool,
+    ) -> Self {
+        ASTNode::VarDeclaration { name, type_hint, value, is_const }
+    }
+    
+    pub fn new_destructuring(
+        names: Vec<String>,
+        from_dictionary: bool,
+        value: Box<ASTNode>,
+        is_const: bool,
+    ) -> Self {
+        ASTNode::Destructuring { names, from_dictionary, value, is_const }
     }
     
     pub fn new_assignment(name: String, value: Box<ASTNode>) -> Self {
         ASTNode::Assignment { name, value }
     }
     
+    pub fn new_step(name: String, delta: f64) -> Self {
+        ASTNode::Step { name, delta }
+    }
+    
     pub fn new_if_statement(
         condition: Box<ASTNode>,
-        then_block: Vec<Box<ASTNode>>,
-        else_block: Option<Vec<Box<ASTNode>>>,
+        then_block: Vec<ASTNode>,
+        else_block: Option<Vec<ASTNode>>,
     ) -> Self {
         ASTNode::IfStatement {
             condition,
@@ -94,27 +174,38 @@ impl ASTNode {
         }
     }
     
-    pub fn new_while_loop(condition: Box<ASTNode>, body: Vec<Box<ASTNode>>) -> Self {
+    pub fn new_while_loop(condition: Box<ASTNode>, body: Vec<ASTNode>) -> Self {
         ASTNode::WhileLoop { condition, body }
     }
     
+    pub fn new_do_while_loop(body: Vec<ASTNode>, condition: Box<ASTNode>) -> Self {
+        ASTNode::DoWhileLoop { body, condition }
+    }
+    
+    pub fn new_repeat_loop(count: Box<ASTNode>, body: Vec<ASTNode>) -> Self {
+        ASTNode::RepeatLoop { count, body }
+    }
+    
     pub fn new_for_each_loop(
+        index: Option<String>,
         variable: String,
         iterable: Box<ASTNode>,
-        body: Vec<Box<ASTNode>>,
+        body: Vec<ASTNode>,
     ) -> Self {
-        ASTNode::ForEachLoop { variable, iterable, body }
+        ASTNode::ForEachLoop { index, variable, iterable, body }
     }
     
     pub fn new_function_declaration(
         name: String,
-        parameters: Vec<String>,
-        body: Vec<Box<ASTNode>>,
+        parameters: Vec<(String, Option<Box<ASTNode>>)>,
+        body: Vec<ASTNode>,
+        is_variadic: bool,
     ) -> Self {
         ASTNode::FunctionDeclaration {
             name,
             parameters,
             body,
+            is_variadic,
         }
     }
     
@@ -130,15 +221,27 @@ impl ASTNode {
         ASTNode::UnaryOp { operator, operand }
     }
     
-    pub fn new_function_call(name: String, arguments: Vec<Box<ASTNode>>) -> Self {
+    pub fn new_function_call(name: String, arguments: Vec<ASTNode>) -> Self {
         ASTNode::FunctionCall { name, arguments }
     }
     
-    pub fn new_list_literal(elements: Vec<Box<ASTNode>>) -> Self {
+    pub fn new_expression_call(callee: Box<ASTNode>, arguments: Vec<ASTNode>) -> Self {
+        ASTNode::ExpressionCall { callee, arguments }
+    }
+    
+    pub fn new_member_access(object: Box<ASTNode>, member: String) -> Self {
+        ASTNode::MemberAccess { object, member }
+    }
+    
+    pub fn new_property_access(object: Box<ASTNode>, property: String) -> Self {
+        ASTNode::PropertyAccess { object, property }
+    }
+    
+    pub fn new_list_literal(elements: Vec<ASTNode>) -> Self {
         ASTNode::ListLiteral(elements)
     }
     
-    pub fn new_dictionary_literal(pairs: Vec<(String, Box<ASTNode>)>) -> Self {
+    pub fn new_dictionary_literal(pairs: Vec<(Box<ASTNode>, Box<ASTNode>)>) -> Self {
         ASTNode::DictionaryLiteral(pairs)
     }
     
@@ -146,11 +249,446 @@ impl ASTNode {
         ASTNode::IndexAccess { object, index }
     }
     
-    pub fn new_import(filename: String) -> Self {
-        ASTNode::Import { filename }
+    pub fn new_safe_index_access(object: Box<ASTNode>, index: Box<ASTNode>) -> Self {
+        ASTNode::SafeIndexAccess { object, index }
+    }
+    
+    pub fn new_chhan_statement(
+        subject: Box<ASTNode>,
+        arms: Vec<(Box<ASTNode>, Vec<ASTNode>)>,
+        default: Option<Vec<ASTNode>>,
+    ) -> Self {
+        ASTNode::ChhanStatement { subject, arms, default }
+    }
+    
+    pub fn new_try_catch(
+        try_block: Vec<ASTNode>,
+        error_name: String,
+        catch_block: Vec<ASTNode>,
+    ) -> Self {
+        ASTNode::TryCatch { try_block, error_name, catch_block }
+    }
+    
+    pub fn new_import(filename: String, alias: Option<String>) -> Self {
+        ASTNode::Import { filename, alias }
+    }
+    
+    pub fn new_module_import(filename: String) -> Self {
+        ASTNode::ModuleImport { filename }
     }
     
     pub fn new_index_assignment(object: Box<ASTNode>, index: Box<ASTNode>, value: Box<ASTNode>) -> Self {
         ASTNode::IndexAssignment { object, index, value }
     }
-}
\ No newline at end of file
+}
+
+// Source text for a node, used by `khukuri fmt`. Statements go one per line
+// with four-space indents; expressions get parentheses only where the
+// parser's precedence needs them, so the output parses back to the same tree.
+// Comments aren't part of the tree, so they don't survive formatting.
+impl fmt::Display for ASTNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = String::new();
+        write_node(self, 0, &mut out);
+        f.write_str(&out)
+    }
+}
+
+const INDENT: &str = "    ";
+
+fn write_node(node: &ASTNode, indent: usize, out: &mut String) {
+    match node {
+        ASTNode::Program(statements) => {
+            for (i, stmt) in statements.iter().enumerate() {
+                if i > 0 {
+                    out.push('\n');
+                    // Functions are set apart from their neighbours by a blank line
+                    let previous = &statements[i - 1];
+                    if matches!(previous, ASTNode::FunctionDeclaration { .. })
+                        || matches!(stmt, ASTNode::FunctionDeclaration { .. }) {
+                        out.push('\n');
+                    }
+                }
+                write_node(stmt, indent, out);
+            }
+        }
+        ASTNode::VarDeclaration { name, type_hint, value, is_const } => {
+            out.push_str(if *is_const { "sthir " } else { "maanau " });
+            out.push_str(name);
+            if let Some(hint) = type_hint {
+                out.push_str(": ");
+                out.push_str(hint);
+            }
+            out.push_str(" = ");
+            write_expr(value, indent, 0, out);
+        }
+        ASTNode::Destructuring { names, from_dictionary, value, is_const } => {
+            out.push_str(if *is_const { "sthir " } else { "maanau " });
+            let (open, close) = if *from_dictionary { ("{", "}") } else { ("[", "]") };
+            out.push_str(&format!("{}{}{} = ", open, names.join(", "), close));
+            write_expr(value, indent, 0, out);
+        }
+        ASTNode::Delete(name) => out.push_str(&format!("birsau {}", name)),
+        ASTNode::Assignment { name, value } => {
+            out.push_str(name);
+            out.push_str(" = ");
+            write_expr(value, indent, 0, out);
+        }
+        ASTNode::Step { name, delta } => {
+            out.push_str(if *delta > 0.0 { "badha " } else { "ghata " });
+            out.push_str(name);
+        }
+        ASTNode::IndexAssignment { object, index, value } => {
+            write_expr(object, indent, POSTFIX, out);
+            out.push('[');
+            write_expr(index, indent, 0, out);
+            out.push_str("] = ");
+            write_expr(value, indent, 0, out);
+        }
+        ASTNode::IfStatement { condition, then_block, else_block } => {
+            write_if(condition, then_block, else_block.as_deref(), indent, false, out);
+        }
+        ASTNode::WhileLoop { condition, body } => {
+            out.push_str("jaba samma ");
+            write_expr(condition, indent, 0, out);
+            out.push(' ');
+            write_block(body, indent, out);
+        }
+        ASTNode::DoWhileLoop { body, condition } => {
+            out.push_str("gara ");
+            write_block(body, indent, out);
+            out.push_str(" jaba samma ");
+            write_expr(condition, indent, 0, out);
+        }
+        ASTNode::ForEachLoop { index, variable, iterable, body } => {
+            out.push_str("pratyek ");
+            if let Some(index) = index {
+                out.push_str(&format!("{}, ", index));
+            }
+            out.push_str(&format!("{} ma ", variable));
+            write_expr(iterable, indent, 0, out);
+            out.push(' ');
+            write_block(body, indent, out);
+        }
+        ASTNode::RepeatLoop { count, body } => {
+            out.push_str("dohoryau ");
+            write_expr(count, indent, 0, out);
+            out.push(' ');
+            write_block(body, indent, out);
+        }
+        ASTNode::ChhanStatement { subject, arms, default } => {
+            out.push_str("chhan ");
+            write_expr(subject, indent, 0, out);
+            out.push_str(" {\n");
+            for (value, block) in arms {
+                out.push_str(&INDENT.repeat(indent + 1));
+                write_expr(value, indent + 1, 0, out);
+                out.push_str(" bhane ");
+                write_block(block, indent + 1, out);
+                out.push('\n');
+            }
+            if let Some(block) = default {
+                out.push_str(&INDENT.repeat(indent + 1));
+                out.push_str("natra ");
+                write_block(block, indent + 1, out);
+                out.push('\n');
+            }
+            out.push_str(&INDENT.repeat(indent));
+            out.push('}');
+        }
+        ASTNode::TryCatch { try_block, error_name, catch_block } => {
+            out.push_str("kosis ");
+            write_block(try_block, indent, out);
+            out.push_str(&format!(" samat {} ", error_name));
+            write_block(catch_block, indent, out);
+        }
+        ASTNode::FunctionDeclaration { name, parameters, body, is_variadic } => {
+            out.push_str(&format!("kaam {}(", name));
+            for (i, (parameter, default)) in parameters.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                if *is_variadic && i + 1 == parameters.len() {
+                    out.push_str("...");
+                }
+                out.push_str(parameter);
+                if let Some(default) = default {
+                    out.push_str(" = ");
+                    write_expr(default, indent, 0, out);
+                }
+            }
+            out.push_str(") ");
+            write_block(body, indent, out);
+        }
+        ASTNode::Return(value) => {
+            out.push_str("pathau");
+            if **value != ASTNode::Null {
+                out.push(' ');
+                write_expr(value, indent, 0, out);
+            }
+        }
+        ASTNode::Throw(value) => {
+            out.push_str("phek ");
+            write_expr(value, indent, 0, out);
+        }
+        ASTNode::Print(values) => {
+            out.push_str("bhan");
+            for (i, value) in values.iter().enumerate() {
+                out.push_str(if i == 0 { " " } else { ", " });
+                write_expr(value, indent, 0, out);
+            }
+        }
+        ASTNode::PrintInline(value) => {
+            out.push_str("likha ");
+            write_expr(value, indent, 0, out);
+        }
+        ASTNode::Break => out.push_str("rok"),
+        ASTNode::Continue => out.push_str("jane"),
+        ASTNode::Import { filename, alias } => {
+            out.push_str("aayaat ");
+            write_string(filename, out);
+            if let Some(alias) = alias {
+                out.push_str(&format!(" naam ma {}", alias));
+            }
+        }
+        expression => write_expr(expression, indent, 0, out),
+    }
+}
+
+fn write_block(block: &[ASTNode], indent: usize, out: &mut String) {
+    if block.is_empty() {
+        out.push_str("{}");
+        return;
+    }
+    out.push_str("{\n");
+    for stmt in block {
+        out.push_str(&INDENT.repeat(indent + 1));
+        write_node(stmt, indent + 1, out);
+        out.push('\n');
+    }
+    out.push_str(&INDENT.repeat(indent));
+    out.push('}');
+}
+
+// yedi as a statement or an expression; an expression whose branches are
+// single expressions stays on one line: yedi c bhane { a } natra { b }
+fn write_if(
+    condition: &ASTNode,
+    then_block: &[ASTNode],
+    else_block: Option<&[ASTNode]>,
+    indent: usize,
+    inline: bool,
+    out: &mut String,
+) {
+    let write_branch = |block: &[ASTNode], out: &mut String| {
+        if inline {
+            out.push_str("{ ");
+            write_expr(&block[0], indent, 0, out);
+            out.push_str(" }");
+        } else {
+            write_block(block, indent, out);
+        }
+    };
+    
+    out.push_str("yedi ");
+    write_expr(condition, indent, 0, out);
+    out.push_str(" bhane ");
+    write_branch(then_block, out);
+    if let Some(block) = else_block {
+        out.push_str(" natra ");
+        write_branch(block, out);
+    }
+}
+
+// Binding strength of each level in the parser, loosest first
+const OR: u8 = 1;
+const AND: u8 = 2;
+const NOT: u8 = 3;
+const COMPARISON: u8 = 4;
+const BITWISE: u8 = 5;
+const SHIFT: u8 = 6;
+const ADDITIVE: u8 = 7;
+const MULTIPLICATIVE: u8 = 8;
+const UNARY: u8 = 9;
+const POSTFIX: u8 = 10;
+
+fn precedence(node: &ASTNode) -> u8 {
+    match node {
+        ASTNode::BinaryOp { operator, .. } => match operator.as_str() {
+            "wa" => OR,
+            "ra" => AND,
+            "==" | "!=" | "<" | ">" | "<=" | ">=" => COMPARISON,
+            "&" | "|" | "^" => BITWISE,
+            "<<" | ">>" => SHIFT,
+            "+" | "-" => ADDITIVE,
+            _ => MULTIPLICATIVE,
+        },
+        ASTNode::UnaryOp { operator, .. } if operator == "hoina" => NOT,
+        ASTNode::UnaryOp { .. } => UNARY,
+        _ => POSTFIX,
+    }
+}
+
+// Writes an expression, in parentheses if it binds looser than `min_precedence`
+fn write_expr(node: &ASTNode, indent: usize, min_precedence: u8, out: &mut String) {
+    if precedence(node) < min_precedence {
+        out.push('(');
+        write_expr(node, indent, 0, out);
+        out.push(')');
+        return;
+    }
+    
+    match node {
+        ASTNode::BinaryOp { left, operator, right } => {
+            let own = precedence(node);
+            // Operators are left-associative; comparisons never chain unparenthesized
+            let left_min = if own == COMPARISON { own + 1 } else { own };
+            write_expr(left, indent, left_min, out);
+            out.push_str(&format!(" {} ", operator));
+            write_expr(right, indent, own + 1, out);
+        }
+        ASTNode::UnaryOp { operator, operand } => {
+            if operator == "hoina" {
+                out.push_str("hoina ");
+                write_expr(operand, indent, NOT, out);
+            } else {
+                out.push_str(operator);
+                // Keep "- -x" from reading as one token
+                let negative = match operand.as_ref() {
+                    ASTNode::UnaryOp { operator, .. } => operator == "-",
+                    ASTNode::Number(n) => n.starts_with('-'),
+                    _ => false,
+                };
+                if negative {
+                    out.push('(');
+                    write_expr(operand, indent, 0, out);
+                    out.push(')');
+                } else {
+                    write_expr(operand, indent, UNARY, out);
+                }
+            }
+        }
+        ASTNode::FunctionCall { name, arguments } => {
+            out.push_str(name);
+            write_arguments(arguments, indent, out);
+        }
+        ASTNode::ExpressionCall { callee, arguments } => {
+            write_expr(callee, indent, POSTFIX, out);
+            write_arguments(arguments, indent, out);
+        }
+        ASTNode::MemberAccess { object, member: name } | ASTNode::PropertyAccess { object, property: name } => {
+            write_expr(object, indent, POSTFIX, out);
+            out.push('.');
+            out.push_str(name);
+        }
+        ASTNode::IndexAccess { object, index } | ASTNode::SafeIndexAccess { object, index } => {
+            write_expr(object, indent, POSTFIX, out);
+            out.push_str(if matches!(node, ASTNode::SafeIndexAccess { .. }) { "?[" } else { "[" });
+            write_expr(index, indent, 0, out);
+            out.push(']');
+        }
+        ASTNode::IfExpression { condition, then_block, else_block } => {
+            let simple = |block: &[ASTNode]| {
+                block.len() == 1 && !matches!(&block[0], ASTNode::IfStatement { .. })
+                    && precedence(&block[0]) > 0 && is_expression(&block[0])
+            };
+            let inline = simple(then_block) && else_block.as_deref().is_none_or(simple);
+            write_if(condition, then_block, else_block.as_deref(), indent, inline, out);
+        }
+        ASTNode::ListLiteral(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_expr(item, indent, 0, out);
+            }
+            out.push(']');
+        }
+        ASTNode::DictionaryLiteral(pairs) => {
+            out.push('{');
+            for (i, (key, value)) in pairs.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_expr(key, indent, 0, out);
+                out.push_str(": ");
+                write_expr(value, indent, 0, out);
+            }
+            out.push('}');
+        }
+        ASTNode::ModuleImport { filename } => {
+            out.push_str("aayaat_le ");
+            write_string(filename, out);
+        }
+        ASTNode::Import { filename, .. } => {
+            out.push_str("aayaat ");
+            write_string(filename, out);
+        }
+        ASTNode::Identifier(name) => out.push_str(name),
+        ASTNode::Number(text) => out.push_str(text),
+        ASTNode::String(text) => write_string(text, out),
+        ASTNode::Boolean(value) => out.push_str(if *value { "sahi" } else { "galat" }),
+        ASTNode::Null => out.push_str("null"),
+        statement => write_node(statement, indent, out),
+    }
+}
+
+fn write_arguments(arguments: &[ASTNode], indent: usize, out: &mut String) {
+    out.push('(');
+    for (i, argument) in arguments.iter().enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        write_expr(argument, indent, 0, out);
+    }
+    out.push(')');
+}
+
+fn is_expression(node: &ASTNode) -> bool {
+    !matches!(
+        node,
+        ASTNode::Program(_)
+            | ASTNode::VarDeclaration { .. }
+            | ASTNode::Destructuring { .. }
+            | ASTNode::Delete(_)
+            | ASTNode::Assignment { .. }
+            | ASTNode::Step { .. }
+            | ASTNode::IndexAssignment { .. }
+            | ASTNode::IfStatement { .. }
+            | ASTNode::WhileLoop { .. }
+            | ASTNode::DoWhileLoop { .. }
+            | ASTNode::ForEachLoop { .. }
+            | ASTNode::RepeatLoop { .. }
+            | ASTNode::ChhanStatement { .. }
+            | ASTNode::TryCatch { .. }
+            | ASTNode::FunctionDeclaration { .. }
+            | ASTNode::Return(_)
+            | ASTNode::Throw(_)
+            | ASTNode::Print(_)
+            | ASTNode::PrintInline(_)
+            | ASTNode::Break
+            | ASTNode::Continue
+    )
+}
+
+// String literal with the escapes the lexer understands
+fn write_string(text: &str, out: &mut String) {
+    out.push('"');
+    for ch in text.chars() {
+        match ch {
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            '\0' => out.push_str("\\0"),
+            '\x07' => out.push_str("\\a"),
+            '\x08' => out.push_str("\\b"),
+            '\x0C' => out.push_str("\\f"),
+            '\x0B' => out.push_str("\\v"),
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
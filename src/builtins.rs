@@ -0,0 +1,617 @@
+use crate::json;
+use crate::value::{range_len, HashableValue, Value};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Runs the built-in function `name` with already-evaluated arguments.
+/// Returns `None` when no built-in has that name.
+pub fn call_builtin(name: &str, args: &[Value]) -> Option<Result<Value, String>> {
+    let result = match name {
+        "entries" => entries(name, args),
+        "dict_from_pairs" => dict_from_pairs(name, args),
+        "tulana" => tulana(name, args),
+        "niramek" => unary_math(name, args, f64::abs),
+        "tala" => unary_math(name, args, f64::floor),
+        "mathi" => unary_math(name, args, f64::ceil),
+        "najik" => unary_math(name, args, f64::round),
+        "bargamul" => bargamul(name, args),
+        "koshis_num" => koshis_num(name, args),
+        "bich_halne" => bich_halne(name, args),
+        "query_string" => query_string(name, args),
+        "barabar_anadar" => barabar_anadar(name, args),
+        "khoj_anadar" => khoj_anadar(name, args),
+        "khoj" => khoj(name, args),
+        "rup" => rup(name, args),
+        "akaar_varnan" => akaar_varnan(name, args),
+        "ghumau" => ghumau(name, args),
+        "kram" => kram(name, args),
+        "kram_kunji" => kram_kunji(name, args),
+        "thulo" => change_case(name, args, str::to_uppercase),
+        "sano" => change_case(name, args, str::to_lowercase),
+        "suru_huncha" => string_check(name, args, |s, part| s.starts_with(part)),
+        "antya_huncha" => string_check(name, args, |s, part| s.ends_with(part)),
+        "shrinkhala" | "shreni" => shrinkhala(name, args),
+        "suchi" => suchi(name, args),
+        "lambai" => lambai(name, args),
+        "samaya" => samaya(name, args),
+        "ananta_ho" => number_check(name, args, f64::is_infinite),
+        "sankhya_ho" => number_check(name, args, |n| !n.is_nan()),
+        "pakka" => pakka(name, args),
+        "satya" => truthiness(name, args, true),
+        "asatya" => truthiness(name, args, false),
+        "pahilo" => list_end(name, args, <[Value]>::first),
+        "antim" => list_end(name, args, <[Value]>::last),
+        "tukro" => tukro(name, args),
+        "ginti" => ginti(name, args),
+        "dashamlav" => dashamlav(name, args),
+        "nepali_ank" => nepali_ank(name, args),
+        "jod_naksha" => jod_naksha(name, args),
+        "chha_key" | "has" => chha_key(name, args),
+        "hatau" => hatau(name, args),
+        "sundar" => sundar(name, args),
+        "json_banau" => json_banau(name, args),
+        "json_padha" => json_padha(name, args),
+        _ => return None,
+    };
+    Some(result)
+}
+
+pub fn expect_arg_count(name: &str, args: &[Value], count: usize) -> Result<(), String> {
+    if args.len() != count {
+        return Err(format!(
+            "Function {} expects {} arguments, got {}",
+            name, count, args.len()
+        ));
+    }
+    Ok(())
+}
+
+pub fn expect_number(name: &str, value: &Value) -> Result<f64, String> {
+    match value {
+        Value::Number(n) => Ok(*n),
+        other => Err(format!("{} lai Number chahincha, {} payo", name, other.get_type())),
+    }
+}
+
+fn expect_list<'a>(name: &str, value: &'a Value) -> Result<&'a Vec<Value>, String> {
+    match value {
+        Value::List(list) => Ok(list),
+        other => Err(format!("{} lai List chahincha, {} payo", name, other.get_type())),
+    }
+}
+
+fn expect_string<'a>(name: &str, value: &'a Value) -> Result<&'a str, String> {
+    match value {
+        Value::String(s) => Ok(s),
+        other => Err(format!("{} lai String chahincha, {} payo", name, other.get_type())),
+    }
+}
+
+fn expect_dictionary<'a>(name: &str, value: &'a Value) -> Result<&'a HashMap<HashableValue, Value>, String> {
+    match value {
+        Value::Dictionary(dict) => Ok(dict),
+        other => Err(format!("{} lai Dictionary chahincha, {} payo", name, other.get_type())),
+    }
+}
+
+// entries(dict) -> [[key, value], ...] sorted by key
+fn entries(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 1)?;
+    match &args[0] {
+        Value::Dictionary(dict) => {
+            let mut keys: Vec<&HashableValue> = dict.keys().collect();
+            keys.sort();
+            let pairs = keys.into_iter()
+                .map(|key| Value::List(vec![key.to_value(), dict[key].clone()]))
+                .collect();
+            Ok(Value::List(pairs))
+        }
+        other => Err(format!("{} lai Dictionary chahincha, {} payo", name, other.get_type())),
+    }
+}
+
+// dict_from_pairs([[key, value], ...]) -> dict, later pairs win
+fn dict_from_pairs(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 1)?;
+    match &args[0] {
+        Value::List(pairs) => {
+            let mut dict = HashMap::new();
+            for (i, pair) in pairs.iter().enumerate() {
+                match pair {
+                    Value::List(items) if items.len() == 2 => {
+                        let key = HashableValue::from_value(&items[0])
+                            .map_err(|e| format!("{}: element {}: {}", name, i, e))?;
+                        dict.insert(key, items[1].clone());
+                    }
+                    other => {
+                        return Err(format!(
+                            "{}: element {} [key, value] pair hoina: {}",
+                            name, i, other
+                        ));
+                    }
+                }
+            }
+            Ok(Value::Dictionary(dict))
+        }
+        other => Err(format!("{} lai List chahincha, {} payo", name, other.get_type())),
+    }
+}
+
+// tulana(a, b) -> -1, 0 or 1
+fn tulana(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 2)?;
+    let ordering = match args[0].compare(&args[1])? {
+        Ordering::Less => -1.0,
+        Ordering::Equal => 0.0,
+        Ordering::Greater => 1.0,
+    };
+    Ok(Value::Number(ordering))
+}
+
+// niramek/tala/mathi/najik: abs, floor, ceil and round of a single number
+fn unary_math(name: &str, args: &[Value], op: fn(f64) -> f64) -> Result<Value, String> {
+    expect_arg_count(name, args, 1)?;
+    let n = expect_number(name, &args[0])?;
+    Ok(Value::Number(op(n)))
+}
+
+// bargamul(x) -> square root, erroring instead of returning NaN for negatives
+fn bargamul(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 1)?;
+    let n = expect_number(name, &args[0])?;
+    if n < 0.0 {
+        return Err(format!("Negative number ko bargamul hudaina: {}", args[0]));
+    }
+    Ok(Value::Number(n.sqrt()))
+}
+
+// koshis_num(x) -> the number x holds, or null when it can't be parsed
+fn koshis_num(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 1)?;
+    let parsed = match &args[0] {
+        Value::Number(n) => Some(*n),
+        // f64::parse also accepts "inf" and "NaN", which aren't numbers in khukuri source
+        Value::String(s) => s.trim().parse::<f64>().ok().filter(|n| n.is_finite()),
+        _ => None,
+    };
+    Ok(parsed.map(Value::Number).unwrap_or(Value::Null))
+}
+
+// bich_halne(list, sep) -> list with sep inserted between neighbouring elements
+fn bich_halne(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 2)?;
+    match &args[0] {
+        Value::List(list) => {
+            let mut result = Vec::with_capacity(list.len() * 2);
+            for (i, item) in list.iter().enumerate() {
+                if i > 0 {
+                    result.push(args[1].clone());
+                }
+                result.push(item.clone());
+            }
+            Ok(Value::List(result))
+        }
+        other => Err(format!("{} lai List chahincha, {} payo", name, other.get_type())),
+    }
+}
+
+// query_string(dict) -> "a=1&b=hello%20world", keys sorted for a stable result
+fn query_string(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 1)?;
+    match &args[0] {
+        Value::Dictionary(dict) => {
+            let mut keys: Vec<&HashableValue> = dict.keys().collect();
+            keys.sort();
+            let pairs: Vec<String> = keys.into_iter()
+                .map(|key| format!("{}={}", url_encode(&key.to_string()), url_encode(&dict[key].to_string())))
+                .collect();
+            Ok(Value::String(pairs.join("&")))
+        }
+        other => Err(format!("{} lai Dictionary chahincha, {} payo", name, other.get_type())),
+    }
+}
+
+// Percent-encodes everything except RFC 3986 unreserved characters
+fn url_encode(text: &str) -> String {
+    let mut encoded = String::new();
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char);
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+// barabar_anadar(a, b) -> whether two strings are equal ignoring case
+fn barabar_anadar(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 2)?;
+    let a = expect_string(name, &args[0])?;
+    let b = expect_string(name, &args[1])?;
+    Ok(Value::Boolean(a.to_lowercase() == b.to_lowercase()))
+}
+
+// khoj_anadar(haystack, needle) -> char index of the first case-insensitive match, or -1
+fn khoj_anadar(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 2)?;
+    let haystack = expect_string(name, &args[0])?;
+    let needle: String = expect_string(name, &args[1])?.chars().flat_map(char::to_lowercase).collect();
+    
+    // Lowercasing can lengthen a char (İ becomes two), so each byte of the
+    // lowercased copy remembers which char of the original it came from
+    let mut lowered = String::new();
+    let mut origin = Vec::new();
+    for (i, ch) in haystack.chars().enumerate() {
+        lowered.extend(ch.to_lowercase());
+        origin.resize(lowered.len(), i);
+    }
+    
+    let index = match lowered.find(&needle) {
+        Some(byte_index) => origin.get(byte_index).copied().unwrap_or(0) as f64, // only "" in "" misses
+        None => -1.0,
+    };
+    Ok(Value::Number(index))
+}
+
+// khoj(list, x) / khoj(text, part) -> index of the first match, or -1
+fn khoj(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 2)?;
+    let index = match &args[0] {
+        Value::List(list) => match list.iter().position(|item| *item == args[1]) {
+            Some(position) => position as f64,
+            None => -1.0,
+        },
+        Value::String(s) => char_index(s, expect_string(name, &args[1])?),
+        other => return Err(format!("{} lai List wa String chahincha, {} payo", name, other.get_type())),
+    };
+    Ok(Value::Number(index))
+}
+
+// rup(template, ...values) -> template with each {} replaced by the next
+// value; {{ and }} stand for literal braces
+fn rup(name: &str, args: &[Value]) -> Result<Value, String> {
+    let Some((template, values)) = args.split_first() else {
+        return Err(format!("Function {} expects at least 1 argument, got 0", name));
+    };
+    let template = expect_string(name, template)?;
+    let placeholders = template.replace("{{", "").matches("{}").count();
+    if placeholders != values.len() {
+        return Err(format!(
+            "{} ma {} ota {{}} chan, tara {} ota value diyo",
+            name, placeholders, values.len()
+        ));
+    }
+    
+    let mut result = String::new();
+    let mut values = values.iter();
+    let mut chars = template.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match (ch, chars.peek()) {
+            ('{', Some('{')) | ('}', Some('}')) => {
+                chars.next();
+                result.push(ch);
+            }
+            ('{', Some('}')) => {
+                chars.next();
+                if let Some(value) = values.next() {
+                    result.push_str(&value.to_string());
+                }
+            }
+            ('{', _) => return Err(format!("{} ma '{{' pachi '}}' wa '{{' chahincha", name)),
+            _ => result.push(ch),
+        }
+    }
+    Ok(Value::String(result))
+}
+
+// Char (not byte) index of needle in haystack, matching how strings are indexed
+fn char_index(haystack: &str, needle: &str) -> f64 {
+    match haystack.find(needle) {
+        Some(byte_index) => haystack[..byte_index].chars().count() as f64,
+        None => -1.0,
+    }
+}
+
+// akaar_varnan(x) -> type description including container contents
+fn akaar_varnan(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 1)?;
+    Ok(Value::String(args[0].describe_shape()))
+}
+
+// ghumau(list) -> reversed copy
+fn ghumau(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 1)?;
+    let mut reversed = expect_list(name, &args[0])?.clone();
+    reversed.reverse();
+    Ok(Value::List(reversed))
+}
+
+// kram(list) -> sorted copy of a list of numbers or a list of strings
+fn kram(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 1)?;
+    let mut sorted = expect_list(name, &args[0])?.clone();
+    check_sortable(name, &sorted)?;
+    sorted.sort_by(|a, b| a.compare(b).unwrap_or(Ordering::Equal));
+    Ok(Value::List(sorted))
+}
+
+// kram_kunji(list, key) -> copy of a list of dictionaries, stably sorted by each one's value at key
+fn kram_kunji(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 2)?;
+    let list = expect_list(name, &args[0])?;
+    let key = HashableValue::from_value(&args[1])?;
+    
+    let mut keyed = Vec::with_capacity(list.len());
+    for item in list {
+        let dict = match item {
+            Value::Dictionary(dict) => dict,
+            other => return Err(format!("{} lai Dictionary haru ko List chahincha, {} payo", name, other.get_type())),
+        };
+        let sort_key = dict.get(&key)
+            .ok_or_else(|| format!("{}: ek dictionary ma key {} chhaina", name, key.repr()))?;
+        keyed.push((sort_key.clone(), item.clone()));
+    }
+    
+    let sort_keys: Vec<Value> = keyed.iter().map(|(sort_key, _)| sort_key.clone()).collect();
+    check_sortable(name, &sort_keys)?;
+    keyed.sort_by(|(a, _), (b, _)| a.compare(b).unwrap_or(Ordering::Equal));
+    Ok(Value::List(keyed.into_iter().map(|(_, item)| item).collect()))
+}
+
+// There's no order across types, so the values must all be numbers or all strings
+fn check_sortable(name: &str, values: &[Value]) -> Result<(), String> {
+    if let Some(first) = values.first() {
+        if !matches!(first, Value::Number(_) | Value::String(_)) {
+            return Err(format!("{} le Number wa String matra sort garcha, {} payo", name, first.get_type()));
+        }
+        if let Some(other) = values.iter().find(|v| v.get_type() != first.get_type()) {
+            return Err(format!(
+                "{}: list ma mixed types cha ({} ra {}), sort garna mildaina",
+                name, first.get_type(), other.get_type()
+            ));
+        }
+    }
+    Ok(())
+}
+
+// thulo/sano: Unicode-aware upper/lower case copies (scripts without case pass through)
+fn change_case(name: &str, args: &[Value], convert: fn(&str) -> String) -> Result<Value, String> {
+    expect_arg_count(name, args, 1)?;
+    let s = expect_string(name, &args[0])?;
+    Ok(Value::String(convert(s)))
+}
+
+// suru_huncha/antya_huncha: starts-with and ends-with checks
+fn string_check(name: &str, args: &[Value], check: fn(&str, &str) -> bool) -> Result<Value, String> {
+    expect_arg_count(name, args, 2)?;
+    let s = expect_string(name, &args[0])?;
+    let part = expect_string(name, &args[1])?;
+    Ok(Value::Boolean(check(s, part)))
+}
+
+// shrinkhala(end) / shrinkhala(start, end) / shrinkhala(start, end, step) -> lazy range, end exclusive
+fn shrinkhala(name: &str, args: &[Value]) -> Result<Value, String> {
+    if args.is_empty() || args.len() > 3 {
+        return Err(format!("Function {} expects 1 to 3 arguments, got {}", name, args.len()));
+    }
+    let numbers = args.iter()
+        .map(|arg| expect_number(name, arg))
+        .collect::<Result<Vec<f64>, String>>()?;
+    let (start, end, step) = match numbers.as_slice() {
+        [end] => (0.0, *end, 1.0),
+        [start, end] => (*start, *end, 1.0),
+        [start, end, step] => (*start, *end, *step),
+        _ => unreachable!(),
+    };
+    if step == 0.0 || !step.is_finite() {
+        return Err(format!("{} ko step 0 hunu hudaina", name));
+    }
+    Ok(Value::Range { start, end, step })
+}
+
+// suchi(x) -> the items of a range, list or string as a new list
+fn suchi(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 1)?;
+    let items = match &args[0] {
+        Value::Range { start, end, step } => (0..range_len(*start, *end, *step))
+            .map(|i| Value::Number(start + i as f64 * step))
+            .collect(),
+        Value::List(list) => list.clone(),
+        Value::String(s) => s.chars().map(|ch| Value::String(ch.to_string())).collect(),
+        other => return Err(format!("{} lai Range, List wa String chahincha, {} payo", name, other.get_type())),
+    };
+    Ok(Value::List(items))
+}
+
+// lambai(x) -> number of items in a list, string, dictionary or range
+fn lambai(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 1)?;
+    let length = length(&args[0])
+        .ok_or_else(|| format!("{} ko lambai hudaina", args[0].get_type()))?;
+    Ok(Value::Number(length as f64))
+}
+
+// Item count of a collection, also behind the `.lambai` property
+pub(crate) fn length(value: &Value) -> Option<usize> {
+    match value {
+        Value::List(list) => Some(list.len()),
+        Value::String(s) => Some(s.chars().count()),
+        Value::Dictionary(dict) => Some(dict.len()),
+        Value::Range { start, end, step } => Some(range_len(*start, *end, *step)),
+        _ => None,
+    }
+}
+
+// samaya() -> seconds since the Unix epoch, with a fractional part for timing
+fn samaya(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 0)?;
+    let elapsed = SystemTime::now().duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("System ghadi milena: {}", e))?;
+    Ok(Value::Number(elapsed.as_secs_f64()))
+}
+
+// satya(x) / asatya(x) -> whether x counts as true in a condition, or the opposite
+fn truthiness(name: &str, args: &[Value], expected: bool) -> Result<Value, String> {
+    expect_arg_count(name, args, 1)?;
+    Ok(Value::Boolean(args[0].is_truthy() == expected))
+}
+
+// ananta_ho/sankhya_ho: whether x is an infinite number / a number that isn't NaN
+fn number_check(name: &str, args: &[Value], check: fn(f64) -> bool) -> Result<Value, String> {
+    expect_arg_count(name, args, 1)?;
+    let result = match &args[0] {
+        Value::Number(n) => check(*n),
+        _ => false,
+    };
+    Ok(Value::Boolean(result))
+}
+
+// pakka(cond, message?) -> null when cond is truthy, otherwise an error with message
+fn pakka(name: &str, args: &[Value]) -> Result<Value, String> {
+    if args.is_empty() || args.len() > 2 {
+        return Err(format!("Function {} expects 1 or 2 arguments, got {}", name, args.len()));
+    }
+    if args[0].is_truthy() {
+        return Ok(Value::Null);
+    }
+    match args.get(1) {
+        Some(message) => Err(message.to_string()),
+        None => Err("Assertion fail bhayo".to_string()),
+    }
+}
+
+// pahilo/antim: the first / last item of a non-empty list
+fn list_end(name: &str, args: &[Value], pick: fn(&[Value]) -> Option<&Value>) -> Result<Value, String> {
+    expect_arg_count(name, args, 1)?;
+    let list = expect_list(name, &args[0])?;
+    pick(list).cloned()
+        .ok_or_else(|| format!("{}: List khali cha", name))
+}
+
+// tukro(list_or_string, start, end) -> items from start up to (not including) end;
+// negative positions count from the end and out-of-range ones are clamped
+fn tukro(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 3)?;
+    let slice_bounds = |len: usize| -> Result<(usize, usize), String> {
+        let start = slice_position(name, &args[1], len)?;
+        let end = slice_position(name, &args[2], len)?;
+        Ok((start, end.max(start)))
+    };
+    
+    match &args[0] {
+        Value::List(list) => {
+            let (start, end) = slice_bounds(list.len())?;
+            Ok(Value::List(list[start..end].to_vec()))
+        }
+        Value::String(s) => {
+            let chars: Vec<char> = s.chars().collect();
+            let (start, end) = slice_bounds(chars.len())?;
+            Ok(Value::String(chars[start..end].iter().collect()))
+        }
+        other => Err(format!("{} lai List wa String chahincha, {} payo", name, other.get_type())),
+    }
+}
+
+fn slice_position(name: &str, value: &Value, len: usize) -> Result<usize, String> {
+    let n = expect_number(name, value)?;
+    if n.fract() != 0.0 {
+        return Err(format!("{} lai purna sankhya chahincha, {} payo", name, value));
+    }
+    let position = if n < 0.0 { len as f64 + n } else { n };
+    Ok(position.clamp(0.0, len as f64) as usize)
+}
+
+// ginti(list, x) -> items equal to x; ginti(s, part) -> non-overlapping occurrences of part
+fn ginti(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 2)?;
+    let count = match &args[0] {
+        Value::List(list) => list.iter().filter(|item| **item == args[1]).count(),
+        Value::String(s) => {
+            let part = expect_string(name, &args[1])?;
+            if part.is_empty() {
+                return Err(format!("{} ma khali String ginna mildaina", name));
+            }
+            s.matches(part).count()
+        }
+        other => return Err(format!("{} lai List wa String chahincha, {} payo", name, other.get_type())),
+    };
+    Ok(Value::Number(count as f64))
+}
+
+// dashamlav(x, n) -> x as a string with exactly n decimal places
+fn dashamlav(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 2)?;
+    let x = expect_number(name, &args[0])?;
+    let places = expect_number(name, &args[1])?;
+    if places < 0.0 || places.fract() != 0.0 {
+        return Err(format!("{} ko precision negative nabhayeko purna sankhya hunu parcha, {} payo", name, args[1]));
+    }
+    Ok(Value::String(format!("{:.*}", places as usize, x)))
+}
+
+// nepali_ank(x) -> x as printed, with Devanagari digits (123 -> "१२३")
+fn nepali_ank(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 1)?;
+    expect_number(name, &args[0])?;
+    let digits = args[0].to_string().chars()
+        .map(|ch| match ch.to_digit(10) {
+            Some(digit) => char::from_u32('०' as u32 + digit).unwrap_or(ch),
+            None => ch,
+        })
+        .collect();
+    Ok(Value::String(digits))
+}
+
+// jod_naksha(a, b) -> new dictionary with a's entries, overridden by b's
+fn jod_naksha(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 2)?;
+    match (&args[0], &args[1]) {
+        (Value::Dictionary(a), Value::Dictionary(b)) => Ok(merge_dictionaries(a, b)),
+        (Value::Dictionary(_), other) | (other, _) => {
+            Err(format!("{} lai Dictionary chahincha, {} payo", name, other.get_type()))
+        }
+    }
+}
+
+// chha_key(dict, key) -> whether dict has key
+fn chha_key(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 2)?;
+    let dict = expect_dictionary(name, &args[0])?;
+    Ok(Value::Boolean(dict.contains_key(&HashableValue::from_value(&args[1])?)))
+}
+
+// hatau(dict, key) -> copy of dict without key; fine if it wasn't there
+fn hatau(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 2)?;
+    let mut dict = expect_dictionary(name, &args[0])?.clone();
+    dict.remove(&HashableValue::from_value(&args[1])?);
+    Ok(Value::Dictionary(dict))
+}
+
+// Shared by jod_naksha and `dict + dict`
+pub fn merge_dictionaries(a: &HashMap<HashableValue, Value>, b: &HashMap<HashableValue, Value>) -> Value {
+    let mut merged = a.clone();
+    merged.extend(b.iter().map(|(key, value)| (key.clone(), value.clone())));
+    Value::Dictionary(merged)
+}
+
+// sundar(x) -> x rendered over several indented lines, for nested data
+fn sundar(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 1)?;
+    Ok(Value::String(args[0].pretty()))
+}
+
+// json_banau(x) -> x as a compact JSON string
+fn json_banau(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 1)?;
+    Ok(Value::String(json::to_json(&args[0])?))
+}
+
+// json_padha(s) -> the value described by the JSON text s
+fn json_padha(name: &str, args: &[Value]) -> Result<Value, String> {
+    expect_arg_count(name, args, 1)?;
+    json::parse(expect_string(name, &args[0])?)
+}
@@ -1,8 +1,24 @@
 use crate::value::Value;
 use std::collections::HashMap;
 
+#[derive(Clone)]
+pub(crate) struct Binding {
+    value: Value,
+    is_const: bool,
+    type_hint: Option<String>, // from `maanau x: Number = ...`, checked in strict mode
+}
+
+pub(crate) type Scope = HashMap<String, Binding>;
+
+#[derive(Clone)]
 pub struct Environment {
-    scopes: Vec<HashMap<String, Value>>,
+    scopes: Vec<Scope>,
+}
+
+impl Default for Environment {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Environment {
@@ -22,17 +38,39 @@ impl Environment {
         }
     }
     
-    pub fn define(&mut self, name: String, value: Value) {
+    pub fn define(&mut self, name: String, value: Value) -> Result<(), String> {
+        self.define_typed(name, value, false, None)
+    }
+    
+    pub fn define_const(&mut self, name: String, value: Value) -> Result<(), String> {
+        self.define_typed(name, value, true, None)
+    }
+    
+    // Redeclaring a name in the same scope replaces it, unless it is a sthir
+    pub fn define_typed(&mut self, name: String, value: Value, is_const: bool, type_hint: Option<String>) 
+        -> Result<(), String> {
         if let Some(scope) = self.scopes.last_mut() {
-            scope.insert(name, value);
+            if scope.get(&name).is_some_and(|binding| binding.is_const) {
+                return Err(format!("Sthir char feri declare garna sakdaina: {}", name));
+            }
+            scope.insert(name, Binding { value, is_const, type_hint });
         }
+        Ok(())
+    }
+    
+    // The type hint of the binding `name` currently refers to, if it has one
+    pub fn type_hint(&self, name: &str) -> Option<&str> {
+        self.scopes.iter().rev()
+            .find_map(|scope| scope.get(name))
+            .and_then(|binding| binding.type_hint.as_deref())
     }
     
     pub fn get(&self, name: &str) -> Option<Value> {
         // Search from innermost to outermost scope
         for scope in self.scopes.iter().rev() {
-            if let Some(value) = scope.get(name) {
-                return Some(value.clone());
+            if let Some(binding) = scope.get(name) {
+                // Reading a variable copies it, so `maanau b = a` never aliases `a`
+                return Some(binding.value.deep_clone());
             }
         }
         None
@@ -41,14 +79,87 @@ impl Environment {
     pub fn set(&mut self, name: &str, value: Value) -> Result<(), String> {
         // Search from innermost to outermost scope
         for scope in self.scopes.iter_mut().rev() {
-            if scope.contains_key(name) {
-                scope.insert(name.to_string(), value);
+            if let Some(binding) = scope.get_mut(name) {
+                if binding.is_const {
+                    return Err(format!("Sthir char badalna sakdaina: {}", name));
+                }
+                binding.value = value;
+                return Ok(());
+            }
+        }
+        Err(format!("Undefined variable: {}", name))
+    }
+    
+    // Removes the innermost binding of `name`; an outer one it shadowed becomes visible again
+    pub fn remove(&mut self, name: &str) -> Result<(), String> {
+        for scope in self.scopes.iter_mut().rev() {
+            if let Some(binding) = scope.get(name) {
+                if binding.is_const {
+                    return Err(format!("Sthir char birsauna sakdaina: {}", name));
+                }
+                scope.remove(name);
                 return Ok(());
             }
         }
         Err(format!("Undefined variable: {}", name))
     }
     
+    // Every name visible from the current scope, innermost first, without duplicates
+    pub fn visible_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for scope in self.scopes.iter().rev() {
+            for name in scope.keys() {
+                if !names.contains(name) {
+                    names.push(name.clone());
+                }
+            }
+        }
+        names
+    }
+    
+    // Removes every scope above the global one so code run next (an imported
+    // module) defines its names globally; hand the result to `reattach_locals`
+    pub(crate) fn detach_locals(&mut self) -> Vec<Scope> {
+        self.scopes.split_off(1)
+    }
+    
+    pub(crate) fn reattach_locals(&mut self, locals: Vec<Scope>) {
+        // Drop anything an erroring module left pushed before restoring
+        self.scopes.truncate(1);
+        self.scopes.extend(locals);
+    }
+    
+    // Variables in the outermost scope
+    pub fn globals(&self) -> impl Iterator<Item = (&String, &Value)> {
+        self.scopes[0].iter().map(|(name, binding)| (name, &binding.value))
+    }
+    
+    // Values of all local (non-global) variables, inner ones shadowing outer ones
+    pub(crate) fn locals(&self) -> HashMap<String, Value> {
+        let mut locals = HashMap::new();
+        for scope in &self.scopes[1..] {
+            for (name, binding) in scope {
+                locals.insert(name.clone(), binding.value.clone());
+            }
+        }
+        locals
+    }
+    
+    // Pops the innermost scope, returning the values it held
+    pub(crate) fn pop_scope_values(&mut self) -> HashMap<String, Value> {
+        if self.scopes.len() <= 1 {
+            return HashMap::new();
+        }
+        self.scopes.pop().unwrap_or_default().into_iter()
+            .map(|(name, binding)| (name, binding.value))
+            .collect()
+    }
+    
+    // Drops scopes left behind by an error, back to `depth` scopes
+    pub(crate) fn truncate_scopes(&mut self, depth: usize) {
+        self.scopes.truncate(depth.max(1));
+    }
+    
     pub fn current_scope_size(&self) -> usize {
         self.scopes.len()
     }
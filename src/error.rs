@@ -1,30 +1,43 @@
 #[derive(Debug)]
 pub enum CompilerError {
-    LexerError { message: String, line: usize, column: usize },
-    ParserError { message: String, line: usize, column: usize },
-    RuntimeError { message: String, line: usize },
+    Lexer { message: String, line: usize, column: usize },
+    Parser { message: String, line: usize, column: usize },
+    Runtime { message: String, line: usize },
 }
 
 impl CompilerError {
+    /// Builds a lexer error from a message ending in "at line N, column C".
+    pub fn lexer(message: &str) -> Self {
+        let (message, line, column) = split_position(message).unwrap_or((message, 0, 0));
+        CompilerError::Lexer { message: message.to_string(), line, column }
+    }
+    
+    /// Builds a parser error, using the position in the message when it has
+    /// one and `line`/`column` otherwise.
+    pub fn parser(message: &str, line: usize, column: usize) -> Self {
+        let (message, line, column) = split_position(message).unwrap_or((message, line, column));
+        CompilerError::Parser { message: message.to_string(), line, column }
+    }
+    
     pub fn display(&self, source_code: &str) {
         let lines: Vec<&str> = source_code.lines().collect();
         
         match self {
-            CompilerError::LexerError { message, line, column } => {
+            CompilerError::Lexer { message, line, column } => {
                 eprintln!("Lexer Error line {} ma, column {}: {}", line, column, message);
                 if *line > 0 && *line <= lines.len() {
                     eprintln!("  {}", lines[*line - 1]);
                     eprintln!("  {}^", " ".repeat(*column - 1));
                 }
             }
-            CompilerError::ParserError { message, line, column } => {
+            CompilerError::Parser { message, line, column } => {
                 eprintln!("Syntax Error line {} ma, column {}: {}", line, column, message);
                 if *line > 0 && *line <= lines.len() {
                     eprintln!("  {}", lines[*line - 1]);
                     eprintln!("  {}^", " ".repeat(*column - 1));
                 }
             }
-            CompilerError::RuntimeError { message, line } => {
+            CompilerError::Runtime { message, line } => {
                 eprintln!("Runtime Error line {} ma: {}", line, message);
                 if *line > 0 && *line <= lines.len() {
                     eprintln!("  {}", lines[*line - 1]);
@@ -34,16 +47,26 @@ impl CompilerError {
     }
 }
 
+// Splits "Expected X at line 3, column 7" into ("Expected X", 3, 7)
+fn split_position(message: &str) -> Option<(&str, usize, usize)> {
+    let (text, position) = message.rsplit_once(" at line ")?;
+    let (line, column) = match position.split_once(", column ") {
+        Some((line, column)) => (line.parse().ok()?, column.parse().ok()?),
+        None => (position.parse().ok()?, 1),
+    };
+    Some((text, line, column))
+}
+
 impl std::fmt::Display for CompilerError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            CompilerError::LexerError { message, line, column } => {
+            CompilerError::Lexer { message, line, column } => {
                 write!(f, "Lexer Error line {} ma, column {}: {}", line, column, message)
             }
-            CompilerError::ParserError { message, line, column } => {
+            CompilerError::Parser { message, line, column } => {
                 write!(f, "Syntax Error line {} ma, column {}: {}", line, column, message)
             }
-            CompilerError::RuntimeError { message, line } => {
+            CompilerError::Runtime { message, line } => {
                 write!(f, "Runtime Error line {} ma: {}", line, message)
             }
         }
@@ -1,11 +1,22 @@
 use crate::ast::ASTNode;
+use crate::builtins;
 use crate::environment::Environment;
-use crate::value::Value;
+use crate::value::{range_len, Function, HashableValue, ModuleScope, Value};
 use crate::lexer::Lexer;
 use crate::parser::Parser;
+use crate::prelude::{self, NativeFunction, PreludeModule};
+use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::io::{self, Write};
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+// Longest string `"..." * n` may build, in bytes
+const MAX_REPEAT_LEN: usize = 1 << 28;
 
 #[derive(Debug)]
 pub enum ControlFlow {
@@ -15,25 +26,251 @@ pub enum ControlFlow {
     None,
 }
 
+pub type PrintCallback = Box<dyn FnMut(&str)>;
+
+/// Saved variable, function and import state, see [`Interpreter::snapshot`].
+#[derive(Clone)]
+pub struct Snapshot {
+    environment: Environment,
+    functions: HashMap<String, Rc<Function>>,
+    imported_modules: HashMap<String, Value>,
+    native_functions: HashMap<String, NativeFunction>,
+}
+
 pub struct Interpreter {
     environment: Environment,
-    functions: HashMap<String, (Vec<String>, Vec<Box<ASTNode>>)>, // (params, body)
-    imported_modules: HashMap<String, bool>, // Track imported modules to prevent circular imports
+    functions: HashMap<String, Rc<Function>>,
+    imported_modules: HashMap<String, Value>, // Export value of each module imported with aayaat
+    prelude: HashMap<String, PreludeModule>, // Modules `aayaat name` loads by name
+    native_functions: HashMap<String, NativeFunction>, // Registered by the host or imported native prelude modules
     importing_stack: Vec<String>, // Track current import chain to prevent circular imports
+    import_base_dirs: Vec<PathBuf>, // Directory of each file being imported, for relative paths
+    out: Box<dyn Write>, // Where `bhan`/`likha` write, stdout unless given a writer
+    print_callback: Option<PrintCallback>, // Receives `bhan` output instead of `out`
+    rng_state: u64, // xorshift state for jhyau
+    strict: bool, // enforce `maanau x: Type` hints
+    lenient_index: bool, // out-of-bounds list/string reads give null instead of an error
+    quiet: bool, // bhan/likha evaluate their values but print nothing
+    float_precision: Option<usize>, // decimal places bhan/likha round numbers to
+    step_budget: Option<u64>, // stop after this many steps, for untrusted programs
+    steps: u64,
+    call_stack: Vec<String>, // names of the kaam currently running, outermost first, for sthiti()
+    call_depth: usize, // kaam calls running in the current module; a kaam declared at 0 is global
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Interpreter {
     pub fn new() -> Self {
+        Self::with_writer(Box::new(io::stdout()))
+    }
+    
+    /// Creates an interpreter whose printed output goes to `out` instead of
+    /// stdout, e.g. a `Vec<u8>` or file to capture what a program prints.
+    pub fn with_writer(out: Box<dyn Write>) -> Self {
         Interpreter {
             environment: Environment::new(),
             functions: HashMap::new(),
             imported_modules: HashMap::new(),
+            prelude: prelude::standard_modules(),
+            native_functions: HashMap::new(),
             importing_stack: Vec::new(),
+            import_base_dirs: Vec::new(),
+            out,
+            print_callback: None,
+            rng_state: Self::time_seed(),
+            strict: false,
+            lenient_index: false,
+            quiet: false,
+            float_precision: None,
+            step_budget: None,
+            steps: 0,
+            call_stack: Vec::new(),
+            call_depth: 0,
         }
     }
     
+    /// Creates an interpreter that stops with an error once the program has
+    /// run `limit` steps, so a runaway loop can't hang the host.
+    pub fn with_step_limit(limit: u64) -> Self {
+        let mut interpreter = Self::new();
+        interpreter.set_step_limit(Some(limit));
+        interpreter
+    }
+    
+    /// Routes every printed line to `callback` instead of stdout, e.g. to show
+    /// output in a GUI widget. A `bhan` line is passed without its trailing
+    /// newline; `likha` text is passed exactly as printed.
+    pub fn on_print(&mut self, callback: PrintCallback) {
+        self.print_callback = Some(callback);
+    }
+    
+    /// Turns type hint checking on or off. When on, `maanau x: Number = ...`
+    /// must be given a Number, and so must every later assignment to `x`.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+    
+    /// When on, reading a list, range or string past its end gives null
+    /// instead of an error. Missing dictionary keys and out-of-bounds
+    /// index assignment are still errors.
+    pub fn set_lenient_index(&mut self, lenient: bool) {
+        self.lenient_index = lenient;
+    }
+    
+    /// When on, `bhan` and `likha` still evaluate what they would print, so
+    /// errors and side effects still happen, but nothing is written.
+    pub fn set_quiet(&mut self, quiet: bool) {
+        self.quiet = quiet;
+    }
+    
+    /// Rounds numbers printed by `bhan` and `likha` to `decimals` places, so
+    /// `0.1 + 0.2` prints as `0.3` rather than `0.30000000000000004`. Only
+    /// the printed text changes, never the values. `None`, the default,
+    /// prints full precision.
+    pub fn set_float_precision(&mut self, decimals: Option<usize>) {
+        self.float_precision = decimals;
+    }
+    
+    /// Sets or removes the step budget, see [`Interpreter::with_step_limit`].
+    /// Each statement and each loop iteration counts as one step.
+    pub fn set_step_limit(&mut self, limit: Option<u64>) {
+        self.step_budget = limit;
+        self.steps = 0;
+    }
+    
+    /// Makes `aayaat` paths in the top-level program resolve from `dir`,
+    /// normally the directory of the program file, instead of the current
+    /// working directory.
+    pub fn set_import_base(&mut self, dir: impl Into<PathBuf>) {
+        self.import_base_dirs = vec![dir.into()];
+    }
+    
+    /// Makes `aayaat name` load `module`, replacing any module registered
+    /// under that name. Named modules are found before files.
+    pub fn register_module(&mut self, name: &str, module: PreludeModule) {
+        self.prelude.insert(name.to_string(), module);
+    }
+    
+    /// Makes the Rust function `function` callable from khukuri code as
+    /// `name(...)`, e.g. to give scripts logging or HTTP access. Native
+    /// functions take precedence over `kaam` functions of the same name.
+    pub fn register_native(&mut self, name: &str, function: impl Fn(&[Value]) -> Result<Value, String> + 'static) {
+        self.native_functions.insert(name.to_string(), Rc::new(function));
+    }
+    
+    /// Top-level variables and their values, sorted by name.
+    pub fn globals(&self) -> Vec<(String, Value)> {
+        let mut globals: Vec<(String, Value)> = self.environment.globals()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        globals.sort_by(|(a, _), (b, _)| a.cmp(b));
+        globals
+    }
+    
+    /// Captures all variables, functions and imported modules so they can be
+    /// rolled back later with [`Interpreter::restore`].
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            environment: self.environment.clone(),
+            functions: self.functions.clone(),
+            imported_modules: self.imported_modules.clone(),
+            native_functions: self.native_functions.clone(),
+        }
+    }
+    
+    /// Rolls the interpreter back to the state captured by `snapshot`.
+    pub fn restore(&mut self, snapshot: Snapshot) {
+        self.environment = snapshot.environment;
+        self.functions = snapshot.functions;
+        self.imported_modules = snapshot.imported_modules;
+        self.native_functions = snapshot.native_functions;
+    }
+    
     pub fn interpret(&mut self, node: &ASTNode) -> Result<Value, String> {
-        match self.interpret_with_control(node)? {
+        let flow = self.interpret_with_control(node)?;
+        Self::top_level_value(flow)
+    }
+    
+    /// Lexes, parses and runs `source` against this interpreter's existing
+    /// state, so variables and functions persist between calls like in the
+    /// REPL. Returns the value of a trailing expression, otherwise `null`.
+    pub fn eval_source(&mut self, source: &str) -> Result<Value, String> {
+        let mut lexer = Lexer::new(source.to_string());
+        let tokens = lexer.tokenize()
+            .map_err(|e| format!("Lexer error: {}", e))?;
+        let mut parser = Parser::new(tokens);
+        let ast = parser.parse()
+            .map_err(|e| format!("Parser error: {}", e))?;
+        
+        self.program_value(&ast)
+    }
+    
+    // Runs a program and returns the value of its trailing expression or of a
+    // top-level `pathau`, otherwise null. On an error the scopes of any calls
+    // or blocks it was inside are dropped, so the next program (the next REPL
+    // line) defines its variables at the top level again.
+    fn program_value(&mut self, ast: &ASTNode) -> Result<Value, String> {
+        let depth = self.environment.current_scope_size();
+        let result = self.run_program(ast);
+        if result.is_err() {
+            self.environment.truncate_scopes(depth);
+        }
+        result
+    }
+    
+    fn run_program(&mut self, ast: &ASTNode) -> Result<Value, String> {
+        let ASTNode::Program(statements) = ast else {
+            return self.interpret(ast);
+        };
+        let Some((last, rest)) = statements.split_last() else {
+            return Ok(Value::Null);
+        };
+        
+        for stmt in rest {
+            match self.interpret_with_control(stmt)? {
+                ControlFlow::None => continue,
+                flow => return Self::top_level_value(flow),
+            }
+        }
+        
+        // Statements have no value, so only a trailing expression produces one
+        if Self::is_expression(last) {
+            self.evaluate_expression(last)
+        } else {
+            self.interpret(last)
+        }
+    }
+    
+    fn is_expression(node: &ASTNode) -> bool {
+        matches!(
+            node,
+            ASTNode::BinaryOp { .. }
+                | ASTNode::UnaryOp { .. }
+                | ASTNode::FunctionCall { .. }
+                | ASTNode::ExpressionCall { .. }
+                | ASTNode::MemberAccess { .. }
+                | ASTNode::PropertyAccess { .. }
+                | ASTNode::IfExpression { .. }
+                | ASTNode::ModuleImport { .. }
+                | ASTNode::ListLiteral(_)
+                | ASTNode::DictionaryLiteral(_)
+                | ASTNode::IndexAccess { .. }
+                | ASTNode::SafeIndexAccess { .. }
+                | ASTNode::Identifier(_)
+                | ASTNode::Number(_)
+                | ASTNode::String(_)
+                | ASTNode::Boolean(_)
+                | ASTNode::Null
+        )
+    }
+    
+    fn top_level_value(flow: ControlFlow) -> Result<Value, String> {
+        match flow {
             ControlFlow::Return(value) => Ok(value),
             ControlFlow::None => Ok(Value::Null),
             ControlFlow::Break => Err("Break statement outside loop".to_string()),
@@ -42,6 +279,7 @@ impl Interpreter {
     }
     
     fn interpret_with_control(&mut self, node: &ASTNode) -> Result<ControlFlow, String> {
+        self.count_step()?;
         match node {
             ASTNode::Program(statements) => {
                 for stmt in statements {
@@ -53,47 +291,94 @@ impl Interpreter {
                 Ok(ControlFlow::None)
             }
             
-            ASTNode::VarDeclaration { name, value, .. } => {
+            ASTNode::VarDeclaration { name, type_hint, value, is_const } => {
                 let val = self.evaluate_expression(value)?;
-                self.environment.define(name.clone(), val);
+                if let Some(hint) = type_hint {
+                    self.check_type_hint(name, hint, &val)?;
+                }
+                self.environment.define_typed(name.clone(), val, *is_const, type_hint.clone())?;
+                Ok(ControlFlow::None)
+            }
+            
+            ASTNode::Destructuring { names, from_dictionary, value, is_const } => {
+                let val = self.evaluate_expression(value)?;
+                let values = if *from_dictionary {
+                    let Value::Dictionary(dict) = val else {
+                        return Err(format!("{{...}} destructuring lai Dictionary chahincha, {} payo", val.get_type()));
+                    };
+                    names.iter()
+                        .map(|name| dict.get(&HashableValue::from(name.as_str())).cloned()
+                            .ok_or_else(|| format!("Destructuring: dictionary ma key '{}' chhaina", name)))
+                        .collect::<Result<Vec<Value>, String>>()?
+                } else {
+                    let Value::List(list) = val else {
+                        return Err(format!("[...] destructuring lai List chahincha, {} payo", val.get_type()));
+                    };
+                    if list.len() != names.len() {
+                        return Err(format!(
+                            "Destructuring ma {} ota naam chan, tara List ma {} ota item",
+                            names.len(), list.len()
+                        ));
+                    }
+                    list
+                };
+                
+                for (name, val) in names.iter().zip(values) {
+                    self.environment.define_typed(name.clone(), val, *is_const, None)?;
+                }
+                Ok(ControlFlow::None)
+            }
+            
+            ASTNode::Delete(name) => {
+                self.environment.remove(name)?;
                 Ok(ControlFlow::None)
             }
             
             ASTNode::Assignment { name, value } => {
                 let val = self.evaluate_expression(value)?;
+                if let Some(hint) = self.environment.type_hint(name) {
+                    self.check_type_hint(name, hint, &val)?;
+                }
                 self.environment.set(name, val)?;
                 Ok(ControlFlow::None)
             }
             
+            ASTNode::Step { name, delta } => {
+                let keyword = if *delta > 0.0 { "badha" } else { "ghata" };
+                let current = self.environment.get(name)
+                    .ok_or_else(|| self.undefined_variable(name))?;
+                let Value::Number(n) = current else {
+                    return Err(format!("{} {}: Number chahincha, {} payo", keyword, name, current.get_type()));
+                };
+                self.environment.set(name, finite_number(n + delta)?)?;
+                Ok(ControlFlow::None)
+            }
+            
             ASTNode::IndexAssignment { object, index, value } => {
-                let index_val = self.evaluate_expression(index)?;
-                let new_value = self.evaluate_expression(value)?;
+                // Walk a chain like grid[i][j] back to the variable it starts from
+                let mut index_nodes = vec![index.as_ref()];
+                let mut target = object.as_ref();
+                while let ASTNode::IndexAccess { object, index } = target {
+                    index_nodes.push(index.as_ref());
+                    target = object.as_ref();
+                }
+                let name = match target {
+                    ASTNode::Identifier(name) => name,
+                    _ => return Err("Invalid left-hand side in index assignment".to_string()),
+                };
                 
-                // Get the object to modify
-                if let ASTNode::Identifier(name) = object.as_ref() {
-                    if let Some(mut obj) = self.environment.get(name) {
-                        match (&mut obj, &index_val) {
-                            (Value::List(list), Value::Number(n)) => {
-                                let idx = *n as usize;
-                                if idx < list.len() {
-                                    list[idx] = new_value;
-                                    self.environment.set(name, obj)?;
-                                } else {
-                                    return Err(format!("List index {} out of bounds", idx));
-                                }
-                            }
-                            (Value::Dictionary(dict), Value::String(key)) => {
-                                dict.insert(key.clone(), new_value);
-                                self.environment.set(name, obj)?;
-                            }
-                            _ => return Err("Invalid index assignment".to_string()),
-                        }
-                    } else {
-                        return Err(format!("Undefined variable: {}", name));
-                    }
-                } else {
-                    return Err("Invalid left-hand side in index assignment".to_string());
+                // Indices outermost first, then the value
+                let mut path = Vec::new();
+                for index_node in index_nodes.into_iter().rev() {
+                    path.push(self.evaluate_expression(index_node)?);
                 }
+                let new_value = self.evaluate_expression(value)?;
+                
+                // Modify a copy of the whole structure, then store it back
+                let mut obj = self.environment.get(name)
+                    .ok_or_else(|| self.undefined_variable(name))?;
+                Self::assign_index(&mut obj, &path, new_value)?;
+                self.environment.set(name, obj)?;
                 
                 Ok(ControlFlow::None)
             }
@@ -132,6 +417,42 @@ impl Interpreter {
                 }
             }
             
+            ASTNode::ChhanStatement { subject, arms, default } => {
+                // The subject is evaluated once; the first arm equal to it runs
+                let subject_value = self.evaluate_expression(subject)?;
+                for (arm_value, block) in arms {
+                    if self.evaluate_expression(arm_value)? == subject_value {
+                        return self.execute_block(block);
+                    }
+                }
+                match default {
+                    Some(block) => self.execute_block(block),
+                    None => Ok(ControlFlow::None),
+                }
+            }
+            
+            ASTNode::Throw(expr) => {
+                let value = self.evaluate_expression(expr)?;
+                Err(value.to_string())
+            }
+            
+            ASTNode::TryCatch { try_block, error_name, catch_block } => {
+                let depth = self.environment.current_scope_size();
+                match self.execute_block(try_block) {
+                    Ok(flow) => Ok(flow),
+                    Err(message) => {
+                        // The failed block may have left its scopes (and those of any
+                        // calls or loops inside it) open
+                        self.environment.truncate_scopes(depth);
+                        self.environment.push_scope();
+                        self.environment.define(error_name.clone(), Value::String(message))?;
+                        let result = self.execute_block(catch_block);
+                        self.environment.pop_scope();
+                        result
+                    }
+                }
+            }
+            
             ASTNode::WhileLoop { condition, body } => {
                 loop {
                     let cond_value = self.evaluate_expression(condition)?;
@@ -139,133 +460,109 @@ impl Interpreter {
                         break;
                     }
                     
-                    self.environment.push_scope();
-                    let mut should_break = false;
-                    
-                    for stmt in body {
-                        match self.interpret_with_control(stmt)? {
-                            ControlFlow::None => continue,
-                            ControlFlow::Break => {
-                                should_break = true;
-                                break;
-                            }
-                            ControlFlow::Continue => break,
-                            flow @ ControlFlow::Return(_) => {
-                                self.environment.pop_scope();
-                                return Ok(flow);
-                            }
-                        }
+                    match self.run_loop_body(Vec::new(), body)? {
+                        ControlFlow::Break => break,
+                        flow @ ControlFlow::Return(_) => return Ok(flow),
+                        _ => {}
+                    }
+                }
+                Ok(ControlFlow::None)
+            }
+            
+            ASTNode::DoWhileLoop { body, condition } => {
+                // Same as WhileLoop, but the body runs once before the first check
+                loop {
+                    match self.run_loop_body(Vec::new(), body)? {
+                        ControlFlow::Break => break,
+                        flow @ ControlFlow::Return(_) => return Ok(flow),
+                        _ => {}
                     }
                     
-                    self.environment.pop_scope();
-                    
-                    if should_break {
+                    let cond_value = self.evaluate_expression(condition)?;
+                    if !cond_value.is_truthy() {
                         break;
                     }
                 }
                 Ok(ControlFlow::None)
             }
             
-            ASTNode::ForEachLoop { variable, iterable, body } => {
+            ASTNode::ForEachLoop { index, variable, iterable, body } => {
                 let iterable_value = self.evaluate_expression(iterable)?;
+                let is_dictionary = matches!(iterable_value, Value::Dictionary(_));
                 
-                match iterable_value {
-                    Value::List(list) => {
-                        for item in list {
-                            self.environment.push_scope();
-                            self.environment.define(variable.clone(), item);
-                            
-                            let mut should_break = false;
-                            for stmt in body {
-                                match self.interpret_with_control(stmt)? {
-                                    ControlFlow::None => continue,
-                                    ControlFlow::Break => {
-                                        should_break = true;
-                                        break;
-                                    }
-                                    ControlFlow::Continue => break,
-                                    flow @ ControlFlow::Return(_) => {
-                                        self.environment.pop_scope();
-                                        return Ok(flow);
-                                    }
-                                }
-                            }
-                            
-                            self.environment.pop_scope();
-                            
-                            if should_break {
-                                break;
-                            }
-                        }
-                    }
-                    Value::Dictionary(dict) => {
-                        for (key, _value) in dict {
-                            self.environment.push_scope();
-                            // For dictionaries, iterate over keys
-                            self.environment.define(variable.clone(), Value::String(key));
-                            
-                            let mut should_break = false;
-                            for stmt in body {
-                                match self.interpret_with_control(stmt)? {
-                                    ControlFlow::None => continue,
-                                    ControlFlow::Break => {
-                                        should_break = true;
-                                        break;
-                                    }
-                                    ControlFlow::Continue => break,
-                                    flow @ ControlFlow::Return(_) => {
-                                        self.environment.pop_scope();
-                                        return Ok(flow);
-                                    }
-                                }
-                            }
-                            
-                            self.environment.pop_scope();
-                            
-                            if should_break {
-                                break;
-                            }
-                        }
-                    }
-                    Value::String(s) => {
-                        for ch in s.chars() {
-                            self.environment.push_scope();
-                            self.environment.define(variable.clone(), Value::String(ch.to_string()));
-                            
-                            let mut should_break = false;
-                            for stmt in body {
-                                match self.interpret_with_control(stmt)? {
-                                    ControlFlow::None => continue,
-                                    ControlFlow::Break => {
-                                        should_break = true;
-                                        break;
-                                    }
-                                    ControlFlow::Continue => break,
-                                    flow @ ControlFlow::Return(_) => {
-                                        self.environment.pop_scope();
-                                        return Ok(flow);
-                                    }
-                                }
-                            }
-                            
-                            self.environment.pop_scope();
-                            
-                            if should_break {
-                                break;
-                            }
-                        }
-                    }
+                // (index or key, item) pairs
+                let items: Box<dyn Iterator<Item = (Value, Value)>> = match iterable_value {
+                    Value::List(list) => Box::new(
+                        list.into_iter().enumerate().map(|(i, item)| (Value::Number(i as f64), item))
+                    ),
+                    Value::Dictionary(dict) => Box::new(dict.into_iter().map(|(key, value)| (key.to_value(), value))),
+                    // Each item is computed from its index so no list is built
+                    Value::Range { start, end, step } => Box::new(
+                        (0..range_len(start, end, step))
+                            .map(move |i| (Value::Number(i as f64), Value::Number(start + i as f64 * step)))
+                    ),
+                    Value::String(s) => Box::new(
+                        s.chars().enumerate()
+                            .map(|(i, ch)| (Value::Number(i as f64), Value::String(ch.to_string())))
+                            .collect::<Vec<_>>().into_iter()
+                    ),
                     _ => return Err(format!("Cannot iterate over {}", iterable_value.get_type())),
+                };
+                
+                for (position, item) in items {
+                    let bindings = match index {
+                        Some(index) => vec![(index.as_str(), position), (variable.as_str(), item)],
+                        // A single variable gets a dictionary's keys, anything else's items
+                        None if is_dictionary => vec![(variable.as_str(), position)],
+                        None => vec![(variable.as_str(), item)],
+                    };
+                    match self.run_loop_body(bindings, body)? {
+                        ControlFlow::Break => break,
+                        flow @ ControlFlow::Return(_) => return Ok(flow),
+                        _ => {}
+                    }
                 }
                 
                 Ok(ControlFlow::None)
             }
             
-            ASTNode::FunctionDeclaration { name, parameters, body } => {
-                self.functions.insert(
-                    name.clone(),
-                    (parameters.clone(), body.clone())
-                );
+            ASTNode::RepeatLoop { count, body } => {
+                let times = match self.evaluate_expression(count)? {
+                    Value::Number(n) if n >= 0.0 && n.fract() == 0.0 => n as u64,
+                    other => return Err(format!(
+                        "dohoryau ko count negative nabhayeko purna sankhya hunu parcha, {} payo", other
+                    )),
+                };
+                
+                for _ in 0..times {
+                    match self.run_loop_body(Vec::new(), body)? {
+                        ControlFlow::Break => break,
+                        flow @ ControlFlow::Return(_) => return Ok(flow),
+                        _ => {}
+                    }
+                }
+                Ok(ControlFlow::None)
+            }
+            
+            ASTNode::FunctionDeclaration { name, parameters, body, is_variadic } => {
+                // A kaam declared inside another kaam is a local variable like any
+                // other, and closes over the locals around it so it still sees them
+                // after that call has returned. Outside any call, even in a block,
+                // it is global.
+                let is_nested = self.call_depth > 0;
+                let function = Rc::new(Function {
+                    name: name.clone(),
+                    parameters: parameters.clone(),
+                    body: body.clone(),
+                    is_variadic: *is_variadic,
+                    module: None,
+                    captured: is_nested.then(|| Rc::new(RefCell::new(self.environment.locals()))),
+                });
+                if is_nested {
+                    self.environment.define(name.clone(), Value::Function(function))?;
+                } else {
+                    self.functions.insert(name.clone(), function);
+                }
                 Ok(ControlFlow::None)
             }
             
@@ -274,17 +571,34 @@ impl Interpreter {
                 Ok(ControlFlow::Return(value))
             }
             
-            ASTNode::Print(expr) => {
+            ASTNode::Print(exprs) => {
+                let mut parts = Vec::new();
+                for expr in exprs {
+                    let value = self.evaluate_expression(expr)?;
+                    parts.push(self.display(&value));
+                }
+                self.write_output(&parts.join(" "), true)?;
+                Ok(ControlFlow::None)
+            }
+            
+            ASTNode::PrintInline(expr) => {
                 let value = self.evaluate_expression(expr)?;
-                println!("{}", value.to_string());
+                let text = self.display(&value);
+                self.write_output(&text, false)?;
                 Ok(ControlFlow::None)
             }
             
-            ASTNode::Import { filename } => {
+            ASTNode::Import { filename, alias: None } => {
                 self.execute_import(filename)?;
                 Ok(ControlFlow::None)
             }
             
+            ASTNode::Import { filename, alias: Some(alias) } => {
+                let module = self.import_module_value(filename)?;
+                self.environment.define(alias.clone(), module)?;
+                Ok(ControlFlow::None)
+            }
+            
             ASTNode::Break => Ok(ControlFlow::Break),
             
             ASTNode::Continue => Ok(ControlFlow::Continue),
@@ -311,6 +625,52 @@ impl Interpreter {
                 self.call_function(name, arguments)
             }
             
+            ASTNode::ExpressionCall { callee, arguments } => {
+                let function = match self.evaluate_expression(callee)? {
+                    Value::Function(function) => function,
+                    other => return Err(format!("{} lai call garna mildaina", other.get_type())),
+                };
+                let mut arg_values = Vec::new();
+                for arg in arguments {
+                    arg_values.push(self.evaluate_expression(arg)?);
+                }
+                self.call_function_value(&function, arg_values)
+            }
+            
+            ASTNode::MemberAccess { object, member } => {
+                match self.evaluate_expression(object)? {
+                    Value::Dictionary(dict) => dict.get(&HashableValue::from(member.as_str()))
+                        .cloned()
+                        .ok_or_else(|| format!("Key '{}' not found in dictionary", member)),
+                    other => Err(format!("{} ma '.{}' access garna mildaina", other.get_type(), member)),
+                }
+            }
+            
+            ASTNode::PropertyAccess { object, property } => {
+                let value = self.evaluate_expression(object)?;
+                if let Value::Dictionary(dict) = &value {
+                    // A key of the same name wins over the property
+                    if let Some(item) = dict.get(&HashableValue::from(property.as_str())) {
+                        return Ok(item.clone());
+                    }
+                }
+                match (property.as_str(), builtins::length(&value)) {
+                    ("lambai", Some(length)) => Ok(Value::Number(length as f64)),
+                    _ if matches!(value, Value::Dictionary(_)) => {
+                        Err(format!("Key '{}' not found in dictionary", property))
+                    }
+                    _ => Err(format!("{} ma '.{}' access garna mildaina", value.get_type(), property)),
+                }
+            }
+            
+            ASTNode::ModuleImport { filename } => self.import_module_value(filename),
+            
+            ASTNode::Import { filename, alias: None } => self.execute_import(filename),
+            
+            ASTNode::IfExpression { condition, then_block, else_block } => {
+                self.if_value(condition, then_block, else_block.as_deref())
+            }
+            
             ASTNode::ListLiteral(elements) => {
                 let mut list = Vec::new();
                 for element in elements {
@@ -322,9 +682,10 @@ impl Interpreter {
             
             ASTNode::DictionaryLiteral(pairs) => {
                 let mut dict = HashMap::new();
-                for (key, value_expr) in pairs {
+                for (key_expr, value_expr) in pairs {
+                    let key = HashableValue::from_value(&self.evaluate_expression(key_expr)?)?;
                     let value = self.evaluate_expression(value_expr)?;
-                    dict.insert(key.clone(), value);
+                    dict.insert(key, value);
                 }
                 Ok(Value::Dictionary(dict))
             }
@@ -332,91 +693,364 @@ impl Interpreter {
             ASTNode::IndexAccess { object, index } => {
                 let obj_val = self.evaluate_expression(object)?;
                 let index_val = self.evaluate_expression(index)?;
-                
-                match (&obj_val, &index_val) {
-                    (Value::List(list), Value::Number(n)) => {
-                        let idx = *n as usize;
-                        if idx < list.len() {
-                            Ok(list[idx].clone())
-                        } else {
-                            Err(format!("List index {} out of bounds", idx))
-                        }
-                    }
-                    (Value::Dictionary(dict), Value::String(key)) => {
-                        dict.get(key)
-                            .cloned()
-                            .ok_or_else(|| format!("Key '{}' not found in dictionary", key))
-                    }
-                    (Value::String(s), Value::Number(n)) => {
-                        let idx = *n as usize;
-                        if idx < s.len() {
-                            let ch = s.chars().nth(idx).unwrap();
-                            Ok(Value::String(ch.to_string()))
-                        } else {
-                            Err(format!("String index {} out of bounds", idx))
-                        }
+                match Self::index_value(&obj_val, &index_val)? {
+                    Some(value) => Ok(value),
+                    None if self.lenient_index && !matches!(obj_val, Value::Dictionary(_)) => Ok(Value::Null),
+                    None => Err(Self::missing_index(&obj_val, &index_val)),
+                }
+            }
+            
+            ASTNode::SafeIndexAccess { object, index } => {
+                let obj_val = self.evaluate_expression(object)?;
+                let index_val = self.evaluate_expression(index)?;
+                match obj_val {
+                    Value::List(_) | Value::Range { .. } | Value::Dictionary(_) | Value::String(_) => {
+                        Ok(Self::index_value(&obj_val, &index_val)?.unwrap_or(Value::Null))
                     }
-                    _ => Err(format!("Cannot index {} with {}", 
-                                   obj_val.get_type(), index_val.get_type()))
+                    _ => Ok(Value::Null),
                 }
             }
             
             ASTNode::Identifier(name) => {
-                self.environment.get(name)
-                    .ok_or_else(|| format!("Undefined variable: {}", name))
+                if let Some(value) = self.environment.get(name) {
+                    return Ok(value);
+                }
+                // A bare function name evaluates to the function itself
+                self.functions.get(name)
+                    .map(|function| Value::Function(function.clone()))
+                    .ok_or_else(|| self.undefined_variable(name))
             }
             
             ASTNode::Number(val) => {
-                val.parse::<f64>()
-                    .map(Value::Number)
-                    .map_err(|_| format!("Invalid number: {}", val))
+                // The lexer keeps the literal as written, '_' separators included.
+                // One too big for f64, like 1e400, is an error rather than inf
+                match val.replace('_', "").parse::<f64>() {
+                    Ok(n) if n.is_finite() => Ok(Value::Number(n)),
+                    Ok(_) => Err(format!("Number {} dherai thulo cha", val)),
+                    Err(_) => Err(format!("Invalid number: {}", val)),
+                }
             }
             
             ASTNode::String(val) => Ok(Value::String(val.clone())),
             
             ASTNode::Boolean(val) => Ok(Value::Boolean(*val)),
             
+            ASTNode::Null => Ok(Value::Null),
+            
             _ => Err("Invalid expression".to_string()),
         }
     }
     
+    fn undefined_variable(&self, name: &str) -> String {
+        let mut message = format!("Undefined variable: {}", name);
+        
+        // Suggest the closest visible name, if it's only a small typo away
+        let suggestion = self.environment.visible_names()
+            .into_iter()
+            .map(|candidate| (edit_distance(name, &candidate), candidate))
+            .filter(|(distance, _)| *distance <= 2 && *distance < name.chars().count())
+            .min();
+        
+        if let Some((_, candidate)) = suggestion {
+            message.push_str(&format!(". Kya tapai le '{}' bhanna khojeko?", candidate));
+        }
+        message
+    }
+    
+    // In strict mode a hinted variable only accepts values of the hinted type
+    fn check_type_hint(&self, name: &str, hint: &str, value: &Value) -> Result<(), String> {
+        if !self.strict {
+            return Ok(());
+        }
+        
+        const TYPES: [&str; 8] = ["Number", "String", "Boolean", "List", "Dictionary", "Function", "Range", "Null"];
+        if !TYPES.contains(&hint) {
+            return Err(format!("Anjaan type hint '{}' ({} ko lagi)", hint, name));
+        }
+        if value.get_type() != hint {
+            return Err(format!(
+                "Type milena: '{}' {} hunu parcha, {} payo",
+                name, hint, value.get_type()
+            ));
+        }
+        Ok(())
+    }
+    
+    // Runs one pass of a loop body in its own scope, binding the loop variable
+    // if there is one. `jane` (even inside nested yedi blocks) only ends this
+    // pass, so it comes back as None; `rok` and `pathau` go to the loop. The
+    // scope is popped on errors too, so a failed loop doesn't leak it.
+    fn run_loop_body(&mut self, bindings: Vec<(&str, Value)>, body: &[ASTNode]) 
+        -> Result<ControlFlow, String> {
+        // Counted here too so a loop with an empty body still uses up steps
+        self.count_step()?;
+        self.environment.push_scope();
+        for (name, value) in bindings {
+            self.environment.define(name.to_string(), value)?;
+        }
+        
+        let mut result = Ok(ControlFlow::None);
+        for stmt in body {
+            match self.interpret_with_control(stmt) {
+                Ok(ControlFlow::None) => continue,
+                Ok(ControlFlow::Continue) => break,
+                other => {
+                    result = other;
+                    break;
+                }
+            }
+        }
+        
+        self.environment.pop_scope();
+        result
+    }
+    
+    // Runs statements in their own scope, stopping at break/continue/return
+    fn execute_block(&mut self, statements: &[ASTNode]) -> Result<ControlFlow, String> {
+        self.environment.push_scope();
+        let mut result = ControlFlow::None;
+        
+        for stmt in statements {
+            result = self.interpret_with_control(stmt)?;
+            if !matches!(result, ControlFlow::None) {
+                break;
+            }
+        }
+        
+        self.environment.pop_scope();
+        Ok(result)
+    }
+    
+    // The item at index, or None when the index is out of bounds or the key is missing
+    fn index_value(obj_val: &Value, index_val: &Value) -> Result<Option<Value>, String> {
+        match (obj_val, index_val) {
+            (Value::List(list), Value::Number(n)) => {
+                let idx = index_position(*n)?;
+                Ok(list.get(idx).cloned())
+            }
+            (Value::Range { start, end, step }, Value::Number(n)) => {
+                let idx = index_position(*n)?;
+                if idx < range_len(*start, *end, *step) {
+                    Ok(Some(Value::Number(start + idx as f64 * step)))
+                } else {
+                    Ok(None)
+                }
+            }
+            (Value::Dictionary(dict), key) => {
+                Ok(dict.get(&dictionary_key(dict, key)?).cloned())
+            }
+            (Value::String(s), Value::Number(n)) => {
+                let idx = index_position(*n)?;
+                // Indices count chars, not bytes
+                Ok(s.chars().nth(idx).map(|ch| Value::String(ch.to_string())))
+            }
+            _ => Err(format!("Cannot index {} with {}",
+                           obj_val.get_type(), index_val.get_type()))
+        }
+    }
+    
+    fn missing_index(obj_val: &Value, index_val: &Value) -> String {
+        match obj_val {
+            Value::Dictionary(_) => format!("Key '{}' not found in dictionary", index_val),
+            other => format!("{} index {} out of bounds", other.get_type(), index_val),
+        }
+    }
+    
+    // yedi in expression position: the taken branch's last expression is the
+    // value, and a branch that ends in a statement (or is missing) gives null
+    fn if_value(
+        &mut self,
+        condition: &ASTNode,
+        then_block: &[ASTNode],
+        else_block: Option<&[ASTNode]>,
+    ) -> Result<Value, String> {
+        let block = if self.evaluate_expression(condition)?.is_truthy() {
+            then_block
+        } else {
+            match else_block {
+                Some(block) => block,
+                None => return Ok(Value::Null),
+            }
+        };
+        
+        self.environment.push_scope();
+        let result = self.block_value(block);
+        self.environment.pop_scope();
+        result
+    }
+    
+    fn block_value(&mut self, statements: &[ASTNode]) -> Result<Value, String> {
+        let Some((last, rest)) = statements.split_last() else {
+            return Ok(Value::Null);
+        };
+        
+        for stmt in rest {
+            self.expect_no_jump(stmt)?;
+        }
+        
+        match last {
+            // A nested yedi at the end of a branch is still part of the expression
+            ASTNode::IfStatement { condition, then_block, else_block } => {
+                self.if_value(condition, then_block, else_block.as_deref())
+            }
+            node if Self::is_expression(node) => self.evaluate_expression(node),
+            node => {
+                self.expect_no_jump(node)?;
+                Ok(Value::Null)
+            }
+        }
+    }
+    
+    // An expression can't return from the function or leave a loop around it
+    fn expect_no_jump(&mut self, stmt: &ASTNode) -> Result<(), String> {
+        match self.interpret_with_control(stmt)? {
+            ControlFlow::None => Ok(()),
+            _ => Err("yedi expression bhitra pathau, rok wa jane garna mildaina".to_string()),
+        }
+    }
+    
+    fn count_step(&mut self) -> Result<(), String> {
+        self.steps += 1;
+        match self.step_budget {
+            Some(budget) if self.steps > budget => {
+                Err("Program dherai lamo bhayo (step limit)".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+    
+    // How bhan/likha show a value, rounded if a float precision is set
+    fn display(&self, value: &Value) -> String {
+        match self.float_precision {
+            Some(decimals) => value.rounded(decimals).to_string(),
+            None => value.to_string(),
+        }
+    }
+    
+    // Sends printed text to the callback if one is set, otherwise to `out`
+    fn write_output(&mut self, text: &str, newline: bool) -> Result<(), String> {
+        if self.quiet {
+            return Ok(());
+        }
+        
+        if let Some(callback) = self.print_callback.as_mut() {
+            callback(text);
+            return Ok(());
+        }
+        
+        let written = if newline {
+            writeln!(self.out, "{}", text)
+        } else {
+            // Without a newline a line-buffered stdout won't show it on its own
+            write!(self.out, "{}", text).and_then(|_| self.out.flush())
+        };
+        written.map_err(|e| format!("Output lekhna sakiyena: {}", e))
+    }
+    
+    // Sets the element at `path` (one index per nesting level) inside `target`
+    fn assign_index(target: &mut Value, path: &[Value], new_value: Value) -> Result<(), String> {
+        let Some((index, rest)) = path.split_first() else {
+            *target = new_value;
+            return Ok(());
+        };
+        
+        let slot = match (target, index) {
+            (Value::List(list), Value::Number(n)) => {
+                let idx = index_position(*n)?;
+                list.get_mut(idx)
+                    .ok_or_else(|| format!("List index {} out of bounds", idx))?
+            }
+            // The last key may be new; keys along the way must already exist
+            (Value::Dictionary(dict), key) if rest.is_empty() => {
+                let key = dictionary_key(dict, key)?;
+                dict.insert(key, new_value);
+                return Ok(());
+            }
+            (Value::Dictionary(dict), key) => {
+                let key = dictionary_key(dict, key)?;
+                dict.get_mut(&key)
+                    .ok_or_else(|| format!("Key '{}' not found in dictionary", key))?
+            }
+            _ => return Err("Invalid index assignment".to_string()),
+        };
+        
+        Self::assign_index(slot, rest, new_value)
+    }
+    
     fn eval_binary_op(&mut self, left: &ASTNode, operator: &str, right: &ASTNode) 
         -> Result<Value, String> {
         let left_val = self.evaluate_expression(left)?;
         let right_val = self.evaluate_expression(right)?;
         
         match (&left_val, operator, &right_val) {
-            (Value::Number(l), "+", Value::Number(r)) => Ok(Value::Number(l + r)),
-            (Value::Number(l), "-", Value::Number(r)) => Ok(Value::Number(l - r)),
-            (Value::Number(l), "*", Value::Number(r)) => Ok(Value::Number(l * r)),
+            (Value::Number(l), "+", Value::Number(r)) => finite_number(l + r),
+            (Value::Number(l), "-", Value::Number(r)) => finite_number(l - r),
+            (Value::Number(l), "*", Value::Number(r)) => finite_number(l * r),
             (Value::Number(l), "/", Value::Number(r)) => {
                 if *r == 0.0 {
                     Err("Division by zero".to_string())
                 } else {
-                    Ok(Value::Number(l / r))
+                    finite_number(l / r)
                 }
             }
             (Value::Number(l), "%", Value::Number(r)) => {
                 if *r == 0.0 {
                     Err("Modulo by zero".to_string())
                 } else {
-                    Ok(Value::Number(l % r))
+                    finite_number(l % r)
                 }
             }
             (Value::Number(l), ">", Value::Number(r)) => Ok(Value::Boolean(l > r)),
             (Value::Number(l), "<", Value::Number(r)) => Ok(Value::Boolean(l < r)),
             (Value::Number(l), ">=", Value::Number(r)) => Ok(Value::Boolean(l >= r)),
             (Value::Number(l), "<=", Value::Number(r)) => Ok(Value::Boolean(l <= r)),
+            // Numbers are all f64, so 1 == 1.0; NaN is unequal to everything, itself included
             (Value::Number(l), "==", Value::Number(r)) => Ok(Value::Boolean(l == r)),
             (Value::Number(l), "!=", Value::Number(r)) => Ok(Value::Boolean(l != r)),
             
+            (Value::Number(l), "&" | "|" | "^" | "<<" | ">>", Value::Number(r)) => {
+                Self::eval_bitwise_op(*l, operator, *r)
+            }
+            
             (Value::String(l), "+", Value::String(r)) => Ok(Value::String(format!("{}{}", l, r))),
+            (Value::Dictionary(l), "+", Value::Dictionary(r)) => Ok(builtins::merge_dictionaries(l, r)),
+            (Value::String(s), "*", Value::Number(n)) | (Value::Number(n), "*", Value::String(s)) => {
+                if n.fract() != 0.0 || *n < 0.0 {
+                    return Err(format!(
+                        "String dohoryauna count purna sankhya (0 wa badi) hunu parcha, payo {}",
+                        Value::Number(*n)
+                    ));
+                }
+                // `as usize` would saturate a count past usize::MAX, so check it first
+                if *n >= usize::MAX as f64 {
+                    return Err(format!("String dohoryauna count dherai thulo cha, payo {}", Value::Number(*n)));
+                }
+                let count = *n as usize;
+                if s.len().checked_mul(count).is_none_or(|length| length > MAX_REPEAT_LEN) {
+                    return Err(format!("String dohoryauda {} bytes bhanda lamo huncha", MAX_REPEAT_LEN));
+                }
+                Ok(Value::String(s.repeat(count)))
+            }
             (Value::String(l), "==", Value::String(r)) => Ok(Value::Boolean(l == r)),
             (Value::String(l), "!=", Value::String(r)) => Ok(Value::Boolean(l != r)),
             
             (Value::Boolean(l), "==", Value::Boolean(r)) => Ok(Value::Boolean(l == r)),
             (Value::Boolean(l), "!=", Value::Boolean(r)) => Ok(Value::Boolean(l != r)),
             
+            // Structural equality; dictionaries ignore key order
+            (Value::List(l), "==", Value::List(r)) => Ok(Value::Boolean(l == r)),
+            (Value::List(l), "!=", Value::List(r)) => Ok(Value::Boolean(l != r)),
+            (Value::Dictionary(l), "==", Value::Dictionary(r)) => Ok(Value::Boolean(l == r)),
+            (Value::Dictionary(l), "!=", Value::Dictionary(r)) => Ok(Value::Boolean(l != r)),
+            
+            // Anything can be checked against null, which equals only null
+            (Value::Null, "==", other) | (other, "==", Value::Null) => {
+                Ok(Value::Boolean(matches!(other, Value::Null)))
+            }
+            (Value::Null, "!=", other) | (other, "!=", Value::Null) => {
+                Ok(Value::Boolean(!matches!(other, Value::Null)))
+            }
+            
             // Logical operators (ra = and, wa = or)
             (l, "ra", r) => Ok(Value::Boolean(l.is_truthy() && r.is_truthy())),
             (l, "wa", r) => Ok(Value::Boolean(l.is_truthy() || r.is_truthy())),
@@ -430,10 +1064,39 @@ impl Interpreter {
             }
             
             _ => Err(format!("Invalid operation: {} {} {}", 
-                           left_val.to_string(), operator, right_val.to_string()))
+                           left_val, operator, right_val))
         }
     }
     
+    // Bitwise operators work on whole numbers, treated as 64-bit integers
+    fn eval_bitwise_op(left: f64, operator: &str, right: f64) -> Result<Value, String> {
+        let to_integer = |n: f64| {
+            if n.fract() == 0.0 && n >= i64::MIN as f64 && n <= i64::MAX as f64 {
+                Ok(n as i64)
+            } else {
+                Err(format!("Bitwise '{}' lai purna sankhya chahincha, {} payo", operator, Value::Number(n)))
+            }
+        };
+        let l = to_integer(left)?;
+        let r = to_integer(right)?;
+        
+        let result = match operator {
+            "&" => l & r,
+            "|" => l | r,
+            "^" => l ^ r,
+            "<<" | ">>" => {
+                let shift = u32::try_from(r)
+                    .ok()
+                    .filter(|shift| *shift < 64)
+                    .ok_or_else(|| format!("Shift amount 0 dekhi 63 samma hunu parcha, {} payo", r))?;
+                if operator == "<<" { l << shift } else { l >> shift }
+            }
+            _ => return Err(format!("Unknown bitwise operator: {}", operator)),
+        };
+        
+        Ok(Value::Number(result as f64))
+    }
+    
     fn eval_unary_op(&mut self, operator: &str, operand: &ASTNode) 
         -> Result<Value, String> {
         let val = self.evaluate_expression(operand)?;
@@ -451,39 +1114,133 @@ impl Interpreter {
         }
     }
     
-    fn call_function(&mut self, name: &str, arguments: &[Box<ASTNode>]) 
+    fn call_function(&mut self, name: &str, arguments: &[ASTNode]) 
+        -> Result<Value, String> {
+        // Evaluate arguments
+        let mut arg_values = Vec::new();
+        for arg in arguments {
+            arg_values.push(self.evaluate_expression(arg)?);
+        }
+        
+        // Native functions from the host first, then declared functions, then
+        // variables holding a function, then built-ins
+        if let Some(native) = self.native_functions.get(name).cloned() {
+            return native(&arg_values);
+        }
+        
+        if let Some(function) = self.functions.get(name).cloned() {
+            return self.call_function_value(&function, arg_values);
+        }
+        
+        if let Some(Value::Function(function)) = self.environment.get(name) {
+            return self.call_function_value(&function, arg_values);
+        }
+        
+        if let Some(result) = self.call_interpreter_builtin(name, &arg_values) {
+            return result;
+        }
+        
+        builtins::call_builtin(name, &arg_values)
+            .unwrap_or_else(|| Err(format!("Undefined function: {}", name)))
+    }
+    
+    fn call_function_value(&mut self, function: &Rc<Function>, arg_values: Vec<Value>) 
+        -> Result<Value, String> {
+        // Popped whether the call returns or fails, so a caught error leaves it balanced
+        self.call_stack.push(function.name.clone());
+        self.call_depth += 1;
+        
+        // Functions exported through aayaat_le run against their own module's globals
+        let result = if let Some(module) = &function.module {
+            let caller_environment = mem::replace(&mut self.environment, module.environment.clone());
+            let caller_functions = mem::replace(&mut self.functions, module.functions.clone());
+            let result = self.run_closure(function, arg_values);
+            self.environment = caller_environment;
+            self.functions = caller_functions;
+            result
+        } else {
+            self.run_closure(function, arg_values)
+        };
+        
+        self.call_depth -= 1;
+        self.call_stack.pop();
+        result
+    }
+    
+    // Captured variables sit in a scope just outside the parameters; whatever
+    // the call leaves in them is saved for the next call
+    fn run_closure(&mut self, function: &Rc<Function>, arg_values: Vec<Value>) 
         -> Result<Value, String> {
-        // Get function definition
-        let (params, body) = self.functions.get(name)
-            .ok_or_else(|| format!("Undefined function: {}", name))?
-            .clone();
+        let Some(captured) = &function.captured else {
+            return self.run_function(function, arg_values);
+        };
         
+        let depth = self.environment.current_scope_size();
+        self.environment.push_scope();
+        // Its own name isn't among the captured locals, so recursion needs it here
+        self.environment.define(function.name.clone(), Value::Function(function.clone()))?;
+        for (name, value) in captured.borrow().iter() {
+            self.environment.define(name.clone(), value.clone())?;
+        }
+        
+        let result = self.run_function(function, arg_values);
+        if result.is_err() {
+            self.environment.truncate_scopes(depth);
+            return result;
+        }
+        let mut values = self.environment.pop_scope_values();
+        for (name, value) in captured.borrow_mut().iter_mut() {
+            if let Some(updated) = values.remove(name) {
+                *value = updated;
+            }
+        }
+        result
+    }
+    
+    fn run_function(&mut self, function: &Function, arg_values: Vec<Value>) 
+        -> Result<Value, String> {
         // Check argument count
-        if arguments.len() != params.len() {
+        if !function.accepts(arg_values.len()) {
+            let required = function.required_parameters();
+            let expected = if function.is_variadic {
+                format!("at least {}", required)
+            } else if required == function.parameters.len() {
+                required.to_string()
+            } else {
+                format!("{} to {}", required, function.parameters.len())
+            };
             return Err(format!(
                 "Function {} expects {} arguments, got {}",
-                name, params.len(), arguments.len()
+                function.name, expected, arg_values.len()
             ));
         }
         
-        // Evaluate arguments
-        let mut arg_values = Vec::new();
-        for arg in arguments {
-            arg_values.push(self.evaluate_expression(arg)?);
-        }
-        
         // Create new scope for function
         self.environment.push_scope();
         
-        // Bind parameters
-        for (param, value) in params.iter().zip(arg_values.iter()) {
-            self.environment.define(param.clone(), value.clone());
+        // Bind parameters; missing trailing ones take their defaults, which are
+        // evaluated in the function scope so they can refer to earlier parameters
+        let mut arg_values = arg_values.into_iter();
+        for (param, default) in function.fixed_parameters() {
+            let value = match (arg_values.next(), default) {
+                (Some(value), _) => value,
+                (None, Some(default)) => self.evaluate_expression(default)?,
+                (None, None) => unreachable!("argument count checked above"),
+            };
+            self.environment.define(param.clone(), value)?;
+        }
+        
+        // A rest parameter collects whatever is left (possibly nothing)
+        if function.is_variadic {
+            if let Some((rest, _)) = function.parameters.last() {
+                self.environment.define(rest.clone(), Value::List(arg_values.collect()))?;
+            }
         }
         
         // Execute function body
         let mut result = Value::Null;
         
-        for stmt in &body {
+        for stmt in &function.body {
             match self.interpret_with_control(stmt)? {
                 ControlFlow::Return(value) => {
                     result = value;
@@ -501,24 +1258,211 @@ impl Interpreter {
         Ok(result)
     }
     
-    fn execute_import(&mut self, filename: &str) -> Result<(), String> {
-        // Check if already imported - if so, skip
-        if self.imported_modules.contains_key(filename) {
-            return Ok(()); // Already imported, skip
+    // Built-ins that need to call back into the interpreter
+    fn call_interpreter_builtin(&mut self, name: &str, args: &[Value]) 
+        -> Option<Result<Value, String>> {
+        let result = match name {
+            "kram_with" => self.kram_with(name, args),
+            "jhyau" => self.jhyau(name, args),
+            "napa" => self.napa(name, args),
+            "sthiti" => self.sthiti(name, args),
+            _ => return None,
+        };
+        Some(result)
+    }
+    
+    // sthiti() -> names of the functions being run, outermost first
+    fn sthiti(&mut self, name: &str, args: &[Value]) -> Result<Value, String> {
+        builtins::expect_arg_count(name, args, 0)?;
+        Ok(Value::List(self.call_stack.iter().map(|name| Value::String(name.clone())).collect()))
+    }
+    
+    // jhyau(min, max) -> random whole number in [min, max]
+    fn jhyau(&mut self, name: &str, args: &[Value]) -> Result<Value, String> {
+        builtins::expect_arg_count(name, args, 2)?;
+        let min = builtins::expect_number(name, &args[0])?;
+        let max = builtins::expect_number(name, &args[1])?;
+        if min.fract() != 0.0 || max.fract() != 0.0 {
+            return Err(format!("{} lai purna sankhya chahincha, {} ra {} payo", name, args[0], args[1]));
+        }
+        if min > max {
+            return Err(format!("{}: min ({}) max ({}) bhanda thulo huna mildaina", name, args[0], args[1]));
         }
         
-        // Check for circular imports in current import chain
-        if self.importing_stack.contains(&filename.to_string()) {
-            return Err(format!("Circular import bhettayo bro: {}", filename));
+        // Past 2^53 whole numbers stop being exact as f64, so wider ranges are refused
+        let span = (max as i128).checked_sub(min as i128)
+            .and_then(|difference| difference.checked_add(1))
+            .filter(|&span| span <= 1 << 53)
+            .ok_or_else(|| format!("{}: range {} dekhi {} samma dherai thulo cha", name, args[0], args[1]))?;
+        Ok(Value::Number(min + (self.next_random() % span as u64) as f64))
+    }
+    
+    // napa(kaam) -> milliseconds taken to call kaam with no arguments
+    fn napa(&mut self, name: &str, args: &[Value]) -> Result<Value, String> {
+        builtins::expect_arg_count(name, args, 1)?;
+        let function = match &args[0] {
+            Value::Function(function) => function.clone(),
+            other => return Err(format!("{} lai Function chahincha, {} payo", name, other.get_type())),
+        };
+        if !function.accepts(0) {
+            return Err(format!(
+                "{} le arguments bina call garcha, {} le {} lincha",
+                name, function.name, function.required_parameters()
+            ));
+        }
+        
+        let start = Instant::now();
+        self.call_function_value(&function, Vec::new())?;
+        Ok(Value::Number(start.elapsed().as_secs_f64() * 1000.0))
+    }
+    
+    // xorshift64; good enough for games, not for anything security related
+    fn next_random(&mut self) -> u64 {
+        let mut x = self.rng_state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.rng_state = x;
+        x
+    }
+    
+    fn time_seed() -> u64 {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos() as u64)
+            .unwrap_or(0);
+        // xorshift gets stuck at zero, so never start there
+        nanos | 1
+    }
+    
+    // kram_with(list, cmp) -> stable sorted copy, ordered by the sign of cmp(a, b)
+    fn kram_with(&mut self, name: &str, args: &[Value]) -> Result<Value, String> {
+        builtins::expect_arg_count(name, args, 2)?;
+        let (list, comparator) = match (&args[0], &args[1]) {
+            (Value::List(list), Value::Function(function)) => (list, function.clone()),
+            (Value::List(_), other) => {
+                return Err(format!("{} ko comparator Function hunu parcha, {} payo", name, other.get_type()));
+            }
+            (other, _) => {
+                return Err(format!("{} lai List chahincha, {} payo", name, other.get_type()));
+            }
+        };
+        
+        if !comparator.accepts(2) {
+            return Err(format!(
+                "{} ko comparator le 2 arguments linu parcha, {} le {} lincha",
+                name, comparator.name, comparator.parameters.len()
+            ));
+        }
+        
+        // sort_by can't fail, so remember the first comparator error and stop comparing
+        let mut sorted = list.clone();
+        let mut error = None;
+        sorted.sort_by(|a, b| {
+            if error.is_some() {
+                return Ordering::Equal;
+            }
+            match self.call_function_value(&comparator, vec![a.clone(), b.clone()]) {
+                Ok(Value::Number(n)) => n.partial_cmp(&0.0).unwrap_or(Ordering::Equal),
+                Ok(other) => {
+                    error = Some(format!("{} ko comparator le Number pathaunu parcha, {} payo", name, other.get_type()));
+                    Ordering::Equal
+                }
+                Err(e) => {
+                    error = Some(e);
+                    Ordering::Equal
+                }
+            }
+        });
+        
+        match error {
+            Some(e) => Err(e),
+            None => Ok(Value::List(sorted)),
+        }
+    }
+    
+    // Runs a module once and returns its export value: its trailing
+    // expression or top-level `pathau`. Later imports reuse the cached value.
+    fn execute_import(&mut self, filename: &str) -> Result<Value, String> {
+        // Modules are tracked by resolved path so the same file reached
+        // through different relative paths is only imported once; named
+        // prelude modules are tracked as <name>
+        let named = self.prelude.get(filename).cloned();
+        let module_key = match named {
+            Some(_) => format!("<{}>", filename),
+            None => self.resolve_import_path(filename).to_string_lossy().to_string(),
+        };
+        
+        if let Some(exported) = self.imported_modules.get(&module_key) {
+            return Ok(exported.clone());
+        }
+        
+        if let Some(PreludeModule::Native(functions)) = named {
+            self.native_functions.extend(functions);
+            self.imported_modules.insert(module_key, Value::Null);
+            return Ok(Value::Null);
+        }
+        
+        // The module's top-level variables and functions become globals of the
+        // importing program, even when `aayaat` appears inside a block or function
+        let locals = self.environment.detach_locals();
+        let result = self.load_module(filename);
+        self.environment.reattach_locals(locals);
+        // A failed import isn't cached, so importing it again fails again
+        if let Ok(exported) = &result {
+            self.imported_modules.insert(module_key, exported.clone());
+        }
+        result
+    }
+    
+    // aayaat_le: runs the module in a fresh environment and returns its
+    // top-level variables and functions as a dictionary
+    fn import_module_value(&mut self, filename: &str) -> Result<Value, String> {
+        if let Some(PreludeModule::Native(_)) = self.prelude.get(filename) {
+            return Err(format!("Native module '{}' lai value ko rup ma import garna mildaina, 'aayaat {}' gara", filename, filename));
         }
         
-        // Add to import stack
-        self.importing_stack.push(filename.to_string());
+        let caller_environment = mem::take(&mut self.environment);
+        let caller_functions = mem::take(&mut self.functions);
+        let result = self.load_module(filename);
+        let module = Rc::new(ModuleScope {
+            environment: mem::replace(&mut self.environment, caller_environment),
+            functions: mem::replace(&mut self.functions, caller_functions),
+        });
+        result?;
+        
+        let mut exports: HashMap<HashableValue, Value> = module.environment.globals()
+            .map(|(name, value)| (HashableValue::from(name.as_str()), value.clone()))
+            .collect();
+        for (name, function) in &module.functions {
+            let exported = Function { module: Some(module.clone()), ..function.as_ref().clone() };
+            exports.insert(HashableValue::from(name.as_str()), Value::Function(Rc::new(exported)));
+        }
+        Ok(Value::Dictionary(exports))
+    }
+    
+    // Runs a named prelude module's source, or else the file `filename`
+    fn load_module(&mut self, filename: &str) -> Result<Value, String> {
+        if let Some(PreludeModule::Source(source)) = self.prelude.get(filename) {
+            let source = source.clone();
+            // Imports inside a prelude module resolve like the importer's own
+            let base_dir = self.import_base_dirs.last().cloned().unwrap_or_default();
+            return self.run_module(filename, format!("<{}>", filename), base_dir, source);
+        }
         
-        // Read the file
-        let file_path = Path::new(filename);
-        let source_code = fs::read_to_string(file_path)
+        let file_path = self.resolve_import_path(filename);
+        let source_code = fs::read_to_string(&file_path)
             .map_err(|e| format!("Import error: File '{}' padhna sakiyena: {}", filename, e))?;
+        let base_dir = file_path.parent().map(Path::to_path_buf).unwrap_or_default();
+        self.run_module(filename, file_path.to_string_lossy().to_string(), base_dir, source_code)
+    }
+    
+    // Parses and runs a module's source against the current environment,
+    // returning its export value
+    fn run_module(&mut self, filename: &str, module_key: String, base_dir: PathBuf, source_code: String) -> Result<Value, String> {
+        // Check for circular imports in current import chain
+        if self.importing_stack.contains(&module_key) {
+            return Err(format!("Circular import bhettayo bro: {}", filename));
+        }
         
         // Lexical analysis
         let mut lexer = Lexer::new(source_code);
@@ -530,15 +1474,84 @@ impl Interpreter {
         let ast = parser.parse()
             .map_err(|e| format!("Import error '{}' ma: {}", filename, e))?;
         
-        // Execute the imported module in current environment
-        let result = self.interpret_with_control(&ast)
+        // Add to import stack; imports inside the module resolve from its directory
+        self.importing_stack.push(module_key);
+        self.import_base_dirs.push(base_dir);
+        
+        // The module's own top level isn't inside the importing kaam
+        let call_depth = mem::take(&mut self.call_depth);
+        let result = self.program_value(&ast)
             .map_err(|e| format!("Runtime error imported file '{}' ma: {}", filename, e));
+        self.call_depth = call_depth;
         
-        // Remove from import stack and mark as imported
+        // Remove from import stack
+        self.import_base_dirs.pop();
         self.importing_stack.pop();
-        self.imported_modules.insert(filename.to_string(), true);
         
-        result?;
-        Ok(())
+        result
+    }
+    
+    // Relative paths are joined to the importing file's directory (for the
+    // top-level program, the import base if one was set); absolute paths are
+    // used as written
+    fn resolve_import_path(&self, filename: &str) -> PathBuf {
+        let path = Path::new(filename);
+        match self.import_base_dirs.last() {
+            Some(base) if path.is_relative() => base.join(path),
+            _ => path.to_path_buf(),
+        }
     }
+}
+
+// The key `dict[key]` refers to. A number that isn't a key itself falls back
+// to its string form, so d[5] finds "5" in dictionaries from json_padha
+fn dictionary_key(dict: &HashMap<HashableValue, Value>, key: &Value) -> Result<HashableValue, String> {
+    let hashed = HashableValue::from_value(key)?;
+    if let Value::Number(_) = key {
+        let as_string = HashableValue::String(key.to_string());
+        if !dict.contains_key(&hashed) && dict.contains_key(&as_string) {
+            return Ok(as_string);
+        }
+    }
+    Ok(hashed)
+}
+
+// Arithmetic results must stay finite; inf and NaN would otherwise spread
+// silently through later calculations
+fn finite_number(n: f64) -> Result<Value, String> {
+    if n.is_finite() {
+        Ok(Value::Number(n))
+    } else {
+        Err("Sankhya ko simana naghyo (overflow)".to_string())
+    }
+}
+
+// Indices must be whole and non-negative; casting would silently truncate 2.9
+// to 2 and saturate -1 to 0
+fn index_position(n: f64) -> Result<usize, String> {
+    if n.fract() != 0.0 || !n.is_finite() {
+        return Err(format!("Index purna sankhya hunu parcha, payo {}", Value::Number(n)));
+    }
+    if n < 0.0 {
+        return Err(format!("Index negative huna mildaina, payo {}", Value::Number(n)));
+    }
+    Ok(n as usize)
+}
+
+// Levenshtein distance between two names, counted in chars
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + if ca == cb { 0 } else { 1 };
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    
+    previous[b.len()]
 }
\ No newline at end of file
@@ -0,0 +1,302 @@
+use crate::value::{HashableValue, Value};
+use std::collections::HashMap;
+
+/// Serializes a value as compact JSON. Dictionary keys become strings and
+/// are written in sorted order; functions and ranges have no JSON form.
+pub fn to_json(value: &Value) -> Result<String, String> {
+    let mut out = String::new();
+    write_value(value, &mut out)?;
+    Ok(out)
+}
+
+fn write_value(value: &Value, out: &mut String) -> Result<(), String> {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Boolean(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) if !n.is_finite() => {
+            return Err(format!("{} lai JSON ma lekhna mildaina", value));
+        }
+        // Display already leaves whole numbers without a trailing .0
+        Value::Number(_) => out.push_str(&value.to_string()),
+        Value::String(s) => write_string(s, out),
+        Value::List(list) => {
+            out.push('[');
+            for (i, item) in list.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(item, out)?;
+            }
+            out.push(']');
+        }
+        Value::Dictionary(dict) => {
+            let mut entries: Vec<_> = dict.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            out.push('{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                // JSON object keys are always strings
+                write_string(&key.to_string(), out);
+                out.push(':');
+                write_value(item, out)?;
+            }
+            out.push('}');
+        }
+        other => return Err(format!("{} lai JSON ma lekhna mildaina", other.get_type())),
+    }
+    Ok(())
+}
+
+fn write_string(s: &str, out: &mut String) {
+    out.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\x08' => out.push_str("\\b"),
+            '\x0C' => out.push_str("\\f"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Parses a JSON document into a value: objects become dictionaries with
+/// string keys and `null` becomes `Value::Null`.
+pub fn parse(source: &str) -> Result<Value, String> {
+    let mut parser = JsonParser { chars: source.chars().collect(), pos: 0 };
+    parser.skip_whitespace();
+    let value = parser.parse_value()?;
+    parser.skip_whitespace();
+    if parser.pos < parser.chars.len() {
+        return Err(parser.error("document pachi aru kura"));
+    }
+    Ok(value)
+}
+
+struct JsonParser {
+    chars: Vec<char>,
+    pos: usize, // index into chars, reported in errors
+}
+
+impl JsonParser {
+    fn error(&self, message: &str) -> String {
+        format!("JSON galat cha: {} at position {}", message, self.pos)
+    }
+    
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+    
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.pos += 1;
+        }
+    }
+    
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("'{}' chahincha", expected)))
+        }
+    }
+    
+    fn parse_value(&mut self) -> Result<Value, String> {
+        match self.peek() {
+            Some('{') => self.parse_object(),
+            Some('[') => self.parse_array(),
+            Some('"') => Ok(Value::String(self.parse_string()?)),
+            Some('t') => self.parse_word("true", Value::Boolean(true)),
+            Some('f') => self.parse_word("false", Value::Boolean(false)),
+            Some('n') => self.parse_word("null", Value::Null),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.parse_number(),
+            Some(c) => Err(self.error(&format!("anapekshit '{}'", c))),
+            None => Err(self.error("input sakiyo")),
+        }
+    }
+    
+    fn parse_word(&mut self, word: &str, value: Value) -> Result<Value, String> {
+        for expected in word.chars() {
+            if self.peek() != Some(expected) {
+                return Err(self.error(&format!("'{}' chahincha", word)));
+            }
+            self.pos += 1;
+        }
+        Ok(value)
+    }
+    
+    fn parse_number(&mut self) -> Result<Value, String> {
+        let start = self.pos;
+        if self.peek() == Some('-') {
+            self.pos += 1;
+        }
+        
+        // Integer part: 0, or digits not starting with 0
+        match self.peek() {
+            Some('0') => self.pos += 1,
+            Some(c) if c.is_ascii_digit() => self.skip_digits(),
+            _ => return Err(self.error("number ma digit chahincha")),
+        }
+        if self.peek() == Some('.') {
+            self.pos += 1;
+            self.expect_digits()?;
+        }
+        if matches!(self.peek(), Some('e' | 'E')) {
+            self.pos += 1;
+            if matches!(self.peek(), Some('+' | '-')) {
+                self.pos += 1;
+            }
+            self.expect_digits()?;
+        }
+        
+        let text: String = self.chars[start..self.pos].iter().collect();
+        match text.parse::<f64>() {
+            Ok(n) if n.is_finite() => Ok(Value::Number(n)),
+            _ => Err(self.error(&format!("number {} dherai thulo cha", text))),
+        }
+    }
+    
+    fn skip_digits(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+    }
+    
+    fn expect_digits(&mut self) -> Result<(), String> {
+        if !self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            return Err(self.error("number ma digit chahincha"));
+        }
+        self.skip_digits();
+        Ok(())
+    }
+    
+    fn parse_string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut string = String::new();
+        
+        loop {
+            let Some(ch) = self.peek() else {
+                return Err(self.error("string banda bhayena"));
+            };
+            self.pos += 1;
+            match ch {
+                '"' => return Ok(string),
+                '\\' => {
+                    let Some(escape) = self.peek() else {
+                        return Err(self.error("string banda bhayena"));
+                    };
+                    self.pos += 1;
+                    match escape {
+                        '"' => string.push('"'),
+                        '\\' => string.push('\\'),
+                        '/' => string.push('/'),
+                        'n' => string.push('\n'),
+                        'r' => string.push('\r'),
+                        't' => string.push('\t'),
+                        'b' => string.push('\x08'),
+                        'f' => string.push('\x0C'),
+                        'u' => string.push(self.parse_unicode_escape()?),
+                        other => return Err(self.error(&format!("galat escape '\\{}'", other))),
+                    }
+                }
+                c if (c as u32) < 0x20 => return Err(self.error("string bhitra control character")),
+                c => string.push(c),
+            }
+        }
+    }
+    
+    // After "\u": four hex digits, or a surrogate pair written as two escapes
+    fn parse_unicode_escape(&mut self) -> Result<char, String> {
+        let first = self.parse_hex4()?;
+        let code = if (0xD800..0xDC00).contains(&first) {
+            if self.peek() != Some('\\') || self.chars.get(self.pos + 1) != Some(&'u') {
+                return Err(self.error("adhuro surrogate pair"));
+            }
+            self.pos += 2;
+            let second = self.parse_hex4()?;
+            if !(0xDC00..0xE000).contains(&second) {
+                return Err(self.error("galat surrogate pair"));
+            }
+            0x10000 + ((first - 0xD800) << 10) + (second - 0xDC00)
+        } else {
+            first
+        };
+        char::from_u32(code).ok_or_else(|| self.error("galat unicode escape"))
+    }
+    
+    fn parse_hex4(&mut self) -> Result<u32, String> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let digit = self.peek()
+                .and_then(|c| c.to_digit(16))
+                .ok_or_else(|| self.error("\\u pachi 4 hex digit chahincha"))?;
+            code = code * 16 + digit;
+            self.pos += 1;
+        }
+        Ok(code)
+    }
+    
+    fn parse_array(&mut self) -> Result<Value, String> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.pos += 1;
+            return Ok(Value::List(items));
+        }
+        
+        loop {
+            self.skip_whitespace();
+            items.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(']') => {
+                    self.pos += 1;
+                    return Ok(Value::List(items));
+                }
+                _ => return Err(self.error("',' wa ']' chahincha")),
+            }
+        }
+    }
+    
+    fn parse_object(&mut self) -> Result<Value, String> {
+        self.expect('{')?;
+        let mut dict = HashMap::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(Value::Dictionary(dict));
+        }
+        
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.error("object ko key string hunu parcha"));
+            }
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            self.skip_whitespace();
+            let value = self.parse_value()?;
+            dict.insert(HashableValue::String(key), value);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some('}') => {
+                    self.pos += 1;
+                    return Ok(Value::Dictionary(dict));
+                }
+                _ => return Err(self.error("',' wa '}' chahincha")),
+            }
+        }
+    }
+}
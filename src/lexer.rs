@@ -1,3 +1,4 @@
+use crate::error::CompilerError;
 use crate::token::{Token, TokenType};
 use std::collections::HashMap;
 
@@ -7,7 +8,10 @@ pub struct Lexer {
     current_char: Option<char>,
     line: usize,
     column: usize,
+    tab_width: usize, // Distance between tab stops
     keywords: HashMap<String, String>,
+    invalid_characters: Option<Vec<String>>, // collected instead of stopping, see tokenize_all
+    saw_comment: bool,
 }
 
 impl Lexer {
@@ -18,16 +22,27 @@ impl Lexer {
         let mut keywords = HashMap::new();
         // Nepali keywords
         keywords.insert("maanau".to_string(), "maanau".to_string());      // Variable declaration
+        keywords.insert("sthir".to_string(), "sthir".to_string());        // Constant declaration
+        keywords.insert("birsau".to_string(), "birsau".to_string());      // Delete variable
         keywords.insert("yedi".to_string(), "yedi".to_string());          // If
         keywords.insert("bhane".to_string(), "bhane".to_string());        // Then
         keywords.insert("natra".to_string(), "natra".to_string());        // Else
         keywords.insert("jaba".to_string(), "jaba".to_string());          // While (part 1)
         keywords.insert("samma".to_string(), "samma".to_string());        // While (part 2)
+        keywords.insert("gara".to_string(), "gara".to_string());          // Do (do-while)
         keywords.insert("pratyek".to_string(), "pratyek".to_string());    // For each
+        keywords.insert("dohoryau".to_string(), "dohoryau".to_string());  // Repeat N times
+        keywords.insert("chhan".to_string(), "chhan".to_string());        // Match/switch
+        keywords.insert("kosis".to_string(), "kosis".to_string());        // Try
+        keywords.insert("samat".to_string(), "samat".to_string());        // Catch
+        keywords.insert("phek".to_string(), "phek".to_string());          // Throw
+        keywords.insert("badha".to_string(), "badha".to_string());        // Increment
+        keywords.insert("ghata".to_string(), "ghata".to_string());        // Decrement
         keywords.insert("ma".to_string(), "ma".to_string());              // In (for foreach)
         keywords.insert("kaam".to_string(), "kaam".to_string());          // Function
         keywords.insert("pathau".to_string(), "pathau".to_string());      // Return
         keywords.insert("bhan".to_string(), "bhan".to_string());          // Print
+        keywords.insert("likha".to_string(), "likha".to_string());        // Print without newline
         keywords.insert("sodha".to_string(), "sodha".to_string());        // Input
         keywords.insert("rok".to_string(), "rok".to_string());            // Break
         keywords.insert("jane".to_string(), "jane".to_string());          // Continue
@@ -37,6 +52,7 @@ impl Lexer {
         keywords.insert("sahi".to_string(), "sahi".to_string());          // True
         keywords.insert("galat".to_string(), "galat".to_string());        // False
         keywords.insert("aayaat".to_string(), "aayaat".to_string());      // Import
+        keywords.insert("aayaat_le".to_string(), "aayaat_le".to_string()); // Import as module value
         
         Lexer {
             code: chars,
@@ -44,16 +60,58 @@ impl Lexer {
             current_char,
             line: 1,
             column: 1,
+            tab_width: 1,
             keywords,
+            invalid_characters: None,
+            saw_comment: false,
+        }
+    }
+    
+    /// Like `new`, but a tab moves to the next multiple of `tab_width`
+    /// columns, so reported columns match editors that render tabs wider
+    /// than one character.
+    pub fn with_tab_width(code: String, tab_width: usize) -> Self {
+        let mut lexer = Lexer::new(code);
+        lexer.tab_width = tab_width.max(1);
+        lexer
+    }
+    
+    /// Whether a `//` comment was skipped. Comments never reach the tokens,
+    /// so `khukuri fmt` uses this to refuse files it would strip them from.
+    pub fn saw_comment(&self) -> bool {
+        self.saw_comment
+    }
+    
+    /// Like `tokenize`, but skips over unexpected characters and reports
+    /// every one of them at once. Other errors, like an unterminated string,
+    /// still stop lexing and are reported after the characters found so far.
+    pub fn tokenize_all(&mut self) -> Result<Vec<Token>, Vec<CompilerError>> {
+        self.invalid_characters = Some(Vec::new());
+        let result = self.tokenize();
+        let mut errors: Vec<CompilerError> = self.invalid_characters.take().unwrap_or_default()
+            .iter()
+            .map(|message| CompilerError::lexer(message))
+            .collect();
+        
+        match result {
+            Ok(tokens) if errors.is_empty() => Ok(tokens),
+            Ok(_) => Err(errors),
+            Err(message) => {
+                errors.push(CompilerError::lexer(&message));
+                Err(errors)
+            }
         }
     }
     
     fn advance(&mut self) {
-        if let Some('\n') = self.current_char {
-            self.line += 1;
-            self.column = 1;
-        } else {
-            self.column += 1;
+        match self.current_char {
+            Some('\n') => {
+                self.line += 1;
+                self.column = 1;
+            }
+            // Jump to the next tab stop; columns are 1-based, stops are every tab_width
+            Some('\t') => self.column = ((self.column - 1) / self.tab_width + 1) * self.tab_width + 1,
+            _ => self.column += 1,
         }
         
         self.pos += 1;
@@ -86,6 +144,7 @@ impl Lexer {
     fn skip_comment(&mut self) {
         // Skip single-line comments starting with //
         if self.current_char == Some('/') && self.peek() == Some('/') {
+            self.saw_comment = true;
             while let Some(ch) = self.current_char {
                 if ch == '\n' {
                     break;
@@ -95,24 +154,94 @@ impl Lexer {
         }
     }
     
-    fn read_number(&mut self) -> String {
-        let mut number = String::new();
+    fn read_number(&mut self) -> Result<String, String> {
+        let start_line = self.line;
+        let start_column = self.column;
+        let mut raw = String::new(); // Kept as written (1_000, .5) so `khukuri fmt` can print it back
         let mut has_dot = false;
         
         while let Some(ch) = self.current_char {
             if ch.is_ascii_digit() {
-                number.push(ch);
+                raw.push(ch);
                 self.advance();
+            } else if ch == '_' {
+                self.skip_digit_separator(&mut raw, start_line, start_column)?;
             } else if ch == '.' && !has_dot {
                 has_dot = true;
-                number.push(ch);
+                raw.push(ch);
                 self.advance();
             } else {
                 break;
             }
         }
         
-        number
+        // Optional exponent: e/E, optional sign, then at least one digit
+        if let Some(ch @ ('e' | 'E')) = self.current_char {
+            raw.push(ch);
+            self.advance();
+            
+            if let Some(sign @ ('+' | '-')) = self.current_char {
+                raw.push(sign);
+                self.advance();
+            }
+            
+            let mut has_exponent_digits = false;
+            while let Some(ch) = self.current_char {
+                if ch.is_ascii_digit() {
+                    has_exponent_digits = true;
+                    raw.push(ch);
+                    self.advance();
+                } else if ch == '_' {
+                    self.skip_digit_separator(&mut raw, start_line, start_column)?;
+                } else {
+                    break;
+                }
+            }
+            
+            if !has_exponent_digits {
+                return Err(format!(
+                    "Galat number format: {} at line {}, column {}",
+                    raw, start_line, start_column
+                ));
+            }
+        }
+        
+        // A second fractional part (1.2.3, 1e1.5) or letters right after the
+        // number (0x10, 1e5e5, 5abc) are a typo, not two tokens
+        let another_fraction = self.current_char == Some('.') && self.peek().is_some_and(|c| c.is_ascii_digit());
+        if another_fraction || self.current_char.is_some_and(char::is_alphanumeric) {
+            while let Some(ch) = self.current_char {
+                if ch.is_alphanumeric() || ch == '.' || ch == '_' {
+                    raw.push(ch);
+                    self.advance();
+                } else {
+                    break;
+                }
+            }
+            return Err(format!(
+                "Galat number format: {} at line {}, column {}",
+                raw, start_line, start_column
+            ));
+        }
+        
+        Ok(raw)
+    }
+    
+    // A '_' separator is only valid directly between two digits (1_000, not 1__0, 5_ or 1._5)
+    fn skip_digit_separator(&mut self, raw: &mut String, line: usize, column: usize) -> Result<(), String> {
+        let prev_is_digit = raw.chars().last().is_some_and(|c| c.is_ascii_digit());
+        let next_is_digit = self.peek().is_some_and(|c| c.is_ascii_digit());
+        raw.push('_');
+        
+        if !prev_is_digit || !next_is_digit {
+            return Err(format!(
+                "Galat number format: {} ('_' digit haru ko bich ma matra aauna sakcha) at line {}, column {}",
+                raw, line, column
+            ));
+        }
+        
+        self.advance();
+        Ok(())
     }
     
     fn read_identifier(&mut self) -> String {
@@ -132,6 +261,12 @@ impl Lexer {
     
     fn read_string(&mut self) -> Result<String, String> {
         let mut string = String::new();
+        let start_line = self.line;
+        let start_column = self.column;
+        let unterminated = || format!(
+            "Unterminated string literal at line {}, column {}",
+            start_line, start_column
+        );
         self.advance(); // Skip opening quote
         
         while let Some(ch) = self.current_char {
@@ -140,26 +275,36 @@ impl Lexer {
                 return Ok(string);
             } else if ch == '\\' {
                 // Handle escape sequences
+                let (escape_line, escape_column) = (self.line, self.column);
                 self.advance();
                 match self.current_char {
                     Some('n') => string.push('\n'),
                     Some('t') => string.push('\t'),
                     Some('r') => string.push('\r'),
+                    Some('0') => string.push('\0'),
+                    Some('a') => string.push('\x07'),
+                    Some('b') => string.push('\x08'),
+                    Some('f') => string.push('\x0C'),
+                    Some('v') => string.push('\x0B'),
                     Some('\\') => string.push('\\'),
                     Some('"') => string.push('"'),
-                    Some(c) => string.push(c),
-                    None => return Err("Unterminated string literal".to_string()),
+                    // An unknown escape is most likely a typo, so don't guess
+                    Some(c) => return Err(format!(
+                        "Unknown escape sequence '\\{}' at line {}, column {}",
+                        c, escape_line, escape_column
+                    )),
+                    None => return Err(unterminated()),
                 }
                 self.advance();
             } else if ch == '\n' {
-                return Err("Unterminated string literal".to_string());
+                return Err(unterminated());
             } else {
                 string.push(ch);
                 self.advance();
             }
         }
         
-        Err("Unterminated string literal".to_string())
+        Err(unterminated())
     }
     
     fn read_operator(&mut self) -> String {
@@ -185,20 +330,20 @@ impl Lexer {
             Some('>') => {
                 operator.push('>');
                 self.advance();
-                if self.current_char == Some('=') {
-                    operator.push('=');
+                if self.current_char == Some('=') || self.current_char == Some('>') {
+                    operator.push(self.current_char.unwrap());
                     self.advance();
                 }
             }
             Some('<') => {
                 operator.push('<');
                 self.advance();
-                if self.current_char == Some('=') {
-                    operator.push('=');
+                if self.current_char == Some('=') || self.current_char == Some('<') {
+                    operator.push(self.current_char.unwrap());
                     self.advance();
                 }
             }
-            Some(ch @ ('+' | '-' | '*' | '/' | '%')) => {
+            Some(ch @ ('+' | '-' | '*' | '/' | '%' | '&' | '|' | '^')) => {
                 operator.push(ch);
                 self.advance();
             }
@@ -248,9 +393,9 @@ impl Lexer {
                     ));
                 }
                 
-                // Handle numbers
-                ch if ch.is_ascii_digit() => {
-                    let number = self.read_number();
+                // Handle numbers, including a leading-dot fraction like .5
+                ch if ch.is_ascii_digit() || (ch == '.' && self.peek().is_some_and(|c| c.is_ascii_digit())) => {
+                    let number = self.read_number()?;
                     tokens.push(Token::new(
                         TokenType::Number,
                         number,
@@ -259,6 +404,15 @@ impl Lexer {
                     ));
                 }
                 
+                // _5 is a misplaced digit separator, not an identifier
+                '_' if self.peek().is_some_and(|c| c.is_ascii_digit()) => {
+                    let raw = self.read_identifier();
+                    return Err(format!(
+                        "Galat number format: {} ('_' digit haru ko bich ma matra aauna sakcha) at line {}, column {}",
+                        raw, token_line, token_column
+                    ));
+                }
+                
                 // Handle identifiers and keywords
                 ch if ch.is_alphabetic() || ch == '_' => {
                     let identifier = self.read_identifier();
@@ -277,7 +431,7 @@ impl Lexer {
                 }
                 
                 // Handle operators
-                '=' | '!' | '>' | '<' | '+' | '-' | '*' | '/' | '%' => {
+                '=' | '!' | '>' | '<' | '+' | '-' | '*' | '/' | '%' | '&' | '|' | '^' => {
                     let operator = self.read_operator();
                     tokens.push(Token::new(
                         TokenType::Operator,
@@ -342,6 +496,15 @@ impl Lexer {
                     ));
                     self.advance();
                 }
+                ';' => {
+                    tokens.push(Token::new(
+                        TokenType::Semicolon,
+                        ";".to_string(),
+                        token_line,
+                        token_column,
+                    ));
+                    self.advance();
+                }
                 ',' => {
                     tokens.push(Token::new(
                         TokenType::Comma,
@@ -360,20 +523,57 @@ impl Lexer {
                     ));
                     self.advance();
                 }
+                '.' if self.peek() == Some('.') && self.code.get(self.pos + 2) == Some(&'.') => {
+                    tokens.push(Token::new(
+                        TokenType::Ellipsis,
+                        "...".to_string(),
+                        token_line,
+                        token_column,
+                    ));
+                    self.advance();
+                    self.advance();
+                    self.advance();
+                }
+                '?' if self.peek() == Some('[') => {
+                    tokens.push(Token::new(
+                        TokenType::SafeLBracket,
+                        "?[".to_string(),
+                        token_line,
+                        token_column,
+                    ));
+                    self.advance();
+                    self.advance();
+                }
+                '.' => {
+                    tokens.push(Token::new(
+                        TokenType::Dot,
+                        ".".to_string(),
+                        token_line,
+                        token_column,
+                    ));
+                    self.advance();
+                }
                 
                 // Handle unexpected characters
                 _ => {
-                    return Err(format!(
+                    let message = format!(
                         "Unexpected character '{}' at line {}, column {}",
                         ch, token_line, token_column
-                    ));
+                    );
+                    match self.invalid_characters.as_mut() {
+                        Some(invalid) => {
+                            invalid.push(message);
+                            self.advance();
+                        }
+                        None => return Err(message),
+                    }
                 }
             }
         }
         
         // Add EOF token
         tokens.push(Token::new(
-            TokenType::EOF,
+            TokenType::Eof,
             "".to_string(),
             self.line,
             self.column,
@@ -0,0 +1,11 @@
+pub mod token;
+pub mod value;
+pub mod lexer;
+pub mod ast;
+pub mod parser;
+pub mod environment;
+pub mod interpreter;
+pub mod error;
+pub mod builtins;
+pub mod json;
+pub mod prelude;
@@ -1,21 +1,15 @@
 use std::env;
 use std::fs;
+use std::path::Path;
 use std::process;
+use std::time::Instant;
 use std::io::{self, Write};
 
-mod token;
-mod value;
-mod lexer;
-mod ast;
-mod parser;
-mod environment;
-mod interpreter;
-mod error;
-
-use crate::lexer::Lexer;
-use crate::parser::Parser;
-use crate::interpreter::Interpreter;
-use crate::value::Value;
+use khukuri::lexer::Lexer;
+use khukuri::parser::Parser;
+use khukuri::token::TokenType;
+use khukuri::interpreter::Interpreter;
+use khukuri::value::Value;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
@@ -23,6 +17,14 @@ fn main() {
     if args.len() < 2 {
         eprintln!("Usage: khukuri <program.nep>");
         eprintln!("   wa: khukuri --repl");
+        eprintln!("   wa: khukuri --tokens <program.nep>");
+        eprintln!("   wa: khukuri --ast <program.nep>");
+        eprintln!("   wa: khukuri --check <program.nep>");
+        eprintln!("   wa: khukuri fmt <program.nep>");
+        eprintln!("   wa: khukuri --strict <program.nep>");
+        eprintln!("   wa: khukuri --quiet <program.nep>");
+        eprintln!("   wa: khukuri --time <program.nep>");
+        eprintln!("   wa: khukuri --max-steps <n> <program.nep>");
         process::exit(1);
     }
     
@@ -31,20 +33,83 @@ fn main() {
         return;
     }
     
-    let input_file = &args[1];
+    // Debug modes: show the lexer/parser output, check syntax or reformat without running the program
+    if args[1] == "--tokens" || args[1] == "--ast" || args[1] == "--check" || args[1] == "fmt" {
+        if args.len() < 3 {
+            eprintln!("Usage: khukuri {} <program.nep>", args[1]);
+            process::exit(1);
+        }
+        
+        let source_code = fs::read_to_string(&args[2])
+            .expect("Failed to read input file");
+        
+        let result = match args[1].as_str() {
+            "--tokens" => dump_tokens(&source_code),
+            "--ast" => dump_ast(&source_code),
+            "fmt" => format_source(&source_code),
+            _ => check_syntax(&source_code),
+        };
+        
+        if let Err(e) = result {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+        return;
+    }
+    
+    // --strict runs the program with type hints enforced, --quiet hides what it prints,
+    // --time reports how long it took and --max-steps <n> stops a program that runs too long
+    let mut strict = false;
+    let mut quiet = false;
+    let mut timed = false;
+    let mut max_steps = None;
+    let mut rest = &args[1..];
+    while let Some(flag) = rest.first() {
+        match flag.as_str() {
+            "--strict" => strict = true,
+            "--quiet" => quiet = true,
+            "--time" => timed = true,
+            "--max-steps" => {
+                match rest.get(1).and_then(|n| n.parse::<u64>().ok()) {
+                    Some(n) => max_steps = Some(n),
+                    None => {
+                        eprintln!("Usage: khukuri --max-steps <n> <program.nep>");
+                        process::exit(1);
+                    }
+                }
+                rest = &rest[1..];
+            }
+            _ => break,
+        }
+        rest = &rest[1..];
+    }
+    let input_file = match rest.first() {
+        Some(file) => file,
+        None => {
+            eprintln!("Usage: khukuri {} <program.nep>", args[1]);
+            process::exit(1);
+        }
+    };
     
     // Read source code
     let source_code = fs::read_to_string(input_file)
         .expect("Failed to read input file");
     
     // Execute the program
-    if let Err(e) = run_program(&source_code) {
+    // Imports in the program resolve from its own directory
+    let base_dir = Path::new(input_file).parent().unwrap_or(Path::new(""));
+    let start = Instant::now();
+    let result = run_program(&source_code, base_dir, strict, quiet, max_steps);
+    if timed {
+        eprintln!("Samaya lagyo: {:.3} ms", start.elapsed().as_secs_f64() * 1000.0);
+    }
+    if let Err(e) = result {
         eprintln!("{}", e);
         process::exit(1);
     }
 }
 
-fn run_program(source_code: &str) -> Result<(), String> {
+fn run_program(source_code: &str, base_dir: &Path, strict: bool, quiet: bool, max_steps: Option<u64>) -> Result<(), String> {
     // Lexical analysis
     let mut lexer = Lexer::new(source_code.to_string());
     let tokens = lexer.tokenize()
@@ -57,35 +122,155 @@ fn run_program(source_code: &str) -> Result<(), String> {
     
     // Interpret and execute
     let mut interpreter = Interpreter::new();
+    interpreter.set_strict(strict);
+    interpreter.set_quiet(quiet);
+    interpreter.set_step_limit(max_steps);
+    interpreter.set_import_base(base_dir);
     interpreter.interpret(&ast)
         .map_err(|e| format!("Runtime error: {}", e))?;
     
     Ok(())
 }
 
+fn dump_tokens(source_code: &str) -> Result<(), String> {
+    let mut lexer = Lexer::new(source_code.to_string());
+    let tokens = lexer.tokenize()
+        .map_err(|e| format!("Lexer error: {}", e))?;
+    
+    for token in &tokens {
+        println!("{}:{} {:?} {:?}", token.line, token.column, token.token_type, token.value);
+    }
+    
+    Ok(())
+}
+
+fn dump_ast(source_code: &str) -> Result<(), String> {
+    let mut lexer = Lexer::new(source_code.to_string());
+    let tokens = lexer.tokenize()
+        .map_err(|e| format!("Lexer error: {}", e))?;
+    
+    let mut parser = Parser::new(tokens);
+    let ast = parser.parse()
+        .map_err(|e| format!("Parser error: {}", e))?;
+    
+    println!("{:#?}", ast);
+    Ok(())
+}
+
+// Prints the program back as canonically formatted source
+fn format_source(source_code: &str) -> Result<(), String> {
+    let mut lexer = Lexer::new(source_code.to_string());
+    let tokens = lexer.tokenize()
+        .map_err(|e| format!("Lexer error: {}", e))?;
+    
+    // The AST has no comments, so printing it back would silently drop them
+    if lexer.saw_comment() {
+        return Err("fmt le comment bhayeko file format garna sakdaina, comment haru harauchan".to_string());
+    }
+    
+    let mut parser = Parser::new(tokens);
+    let ast = parser.parse()
+        .map_err(|e| format!("Parser error: {}", e))?;
+    
+    println!("{}", ast);
+    Ok(())
+}
+
+// Lexes and parses without running, printing every syntax error found
+fn check_syntax(source_code: &str) -> Result<(), String> {
+    let mut lexer = Lexer::new(source_code.to_string());
+    let tokens = match lexer.tokenize_all() {
+        Ok(tokens) => tokens,
+        Err(errors) => {
+            for error in &errors {
+                error.display(source_code);
+            }
+            return Err(format!("{} ota error bhetiyo", errors.len()));
+        }
+    };
+    
+    let mut parser = Parser::new(tokens);
+    if let Err(errors) = parser.parse_all() {
+        for error in &errors {
+            error.display(source_code);
+        }
+        return Err(format!("{} ota error bhetiyo", errors.len()));
+    }
+    
+    println!("Syntax thik cha");
+    Ok(())
+}
+
 fn run_repl() {
     println!("Khukuri Interpreter REPL");
     println!("Nepali Gen-Z Programming Language");
-    println!("'exit' type gara bandha garna\n");
+    println!("'exit' type gara bandha garna, ':help' commands herna\n");
     
     let mut interpreter = Interpreter::new();
+    let mut buffer = String::new(); // Lines of a block that isn't closed yet
     
     loop {
-        print!(">> ");
+        // '..' shows we're still inside an unfinished block
+        print!("{}", if buffer.is_empty() { ">> " } else { ".. " });
         io::stdout().flush().unwrap();
         
+        // End of input (Ctrl-D or a closed pipe) leaves the REPL like 'exit'
         let mut input = String::new();
-        io::stdin().read_line(&mut input).unwrap();
+        match io::stdin().read_line(&mut input) {
+            Ok(0) => {
+                println!();
+                break;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("Input padhna sakiena: {}", e);
+                break;
+            }
+        }
         
-        let input = input.trim();
-        if input == "exit" {
+        if buffer.is_empty() && input.trim() == "exit" {
             break;
         }
         
-        match run_line(&mut interpreter, input) {
+        // Meta-commands start with ':' and are never parsed as code
+        if buffer.is_empty() && input.trim().starts_with(':') {
+            match input.trim() {
+                ":help" => {
+                    println!(":help  yo sahayata dekhau");
+                    println!(":vars  maanau gareka variables ra tinko value dekhau");
+                    println!(":clear sabai variables ra functions birsera naya suru gara");
+                    println!("exit   REPL bandha gara");
+                }
+                ":vars" => {
+                    for (name, value) in interpreter.globals() {
+                        println!("{} = {}", name, value);
+                    }
+                }
+                ":clear" => {
+                    interpreter = Interpreter::new();
+                    println!("Sabai birsiyo");
+                }
+                other => eprintln!("Thaha nabhayeko command: {} (:help herna)", other),
+            }
+            continue;
+        }
+        
+        // Blank lines outside a block have nothing to run
+        if buffer.is_empty() && input.trim().is_empty() {
+            continue;
+        }
+        
+        buffer.push_str(input.trim_end());
+        buffer.push('\n');
+        if needs_more_input(&buffer) {
+            continue;
+        }
+        
+        let source = std::mem::take(&mut buffer);
+        match interpreter.eval_source(&source) {
             Ok(value) => {
                 if value != Value::Null {
-                    println!("{}", value.to_string());
+                    println!("{}", value);
                 }
             }
             Err(e) => eprintln!("Error bhayo: {}", e),
@@ -93,10 +278,20 @@ fn run_repl() {
     }
 }
 
-fn run_line(interpreter: &mut Interpreter, line: &str) -> Result<Value, String> {
-    let mut lexer = Lexer::new(line.to_string());
-    let tokens = lexer.tokenize()?;
-    let mut parser = Parser::new(tokens);
-    let ast = parser.parse()?;
-    interpreter.interpret(&ast)
-}
\ No newline at end of file
+// Whether the REPL input so far has unclosed braces, parens or brackets.
+// Input that doesn't lex is complete: running it reports the error.
+fn needs_more_input(source: &str) -> bool {
+    let Ok(tokens) = Lexer::new(source.to_string()).tokenize() else {
+        return false;
+    };
+    
+    let mut depth = 0;
+    for token in &tokens {
+        match token.token_type {
+            TokenType::LBrace | TokenType::LParen | TokenType::LBracket | TokenType::SafeLBracket => depth += 1,
+            TokenType::RBrace | TokenType::RParen | TokenType::RBracket => depth -= 1,
+            _ => {}
+        }
+    }
+    depth > 0
+}
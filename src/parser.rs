@@ -1,10 +1,13 @@
 use crate::ast::ASTNode;
+use crate::error::CompilerError;
 use crate::token::{Token, TokenType};
+use std::mem;
 
 pub struct Parser {
     tokens: Vec<Token>,
     pos: usize,
     current_token: Option<Token>,
+    loop_depth: usize, // loops around the statement being parsed, for rok/jane
 }
 
 impl Parser {
@@ -15,6 +18,7 @@ impl Parser {
             tokens,
             pos: 0,
             current_token,
+            loop_depth: 0,
         }
     }
     
@@ -43,8 +47,8 @@ impl Parser {
                 Ok(token)
             } else {
                 Err(format!(
-                    "Expected {:?}, found {:?} at line {}",
-                    token_type, token.token_type, token.line
+                    "Expected {:?}, found {:?} at line {}, column {}",
+                    token_type, token.token_type, token.line, token.column
                 ))
             }
         } else {
@@ -60,8 +64,8 @@ impl Parser {
                 Ok(token)
             } else {
                 Err(format!(
-                    "Expected keyword '{}', found '{}' at line {}",
-                    keyword, token.value, token.line
+                    "Expected keyword '{}', found '{}' at line {}, column {}",
+                    keyword, token.value, token.line, token.column
                 ))
             }
         } else {
@@ -69,6 +73,53 @@ impl Parser {
         }
     }
     
+    // Parses a brace-delimited list of statements: { stmt \n stmt ... }
+    fn parse_block(&mut self) -> Result<Vec<ASTNode>, String> {
+        self.expect(TokenType::LBrace)?;
+        
+        let mut statements = Vec::new();
+        self.skip_newlines();
+        
+        while let Some(ref token) = self.current_token {
+            if token.token_type == TokenType::RBrace {
+                break;
+            }
+            
+            if Self::is_separator(token) {
+                self.advance();
+                continue;
+            }
+            
+            let stmt = self.parse_statement()?;
+            statements.push(stmt);
+            
+            self.skip_separators();
+        }
+        
+        self.expect(TokenType::RBrace)?;
+        
+        Ok(statements)
+    }
+    
+    // Statements end at a newline or a ';'
+    fn is_separator(token: &Token) -> bool {
+        matches!(token.token_type, TokenType::Newline | TokenType::Semicolon)
+    }
+    
+    fn skip_separators(&mut self) {
+        while self.current_token.as_ref().is_some_and(Self::is_separator) {
+            self.advance();
+        }
+    }
+    
+    // A loop's block, inside which rok and jane are allowed
+    fn parse_loop_body(&mut self) -> Result<Vec<ASTNode>, String> {
+        self.loop_depth += 1;
+        let body = self.parse_block();
+        self.loop_depth -= 1;
+        body
+    }
+    
     fn skip_newlines(&mut self) {
         while let Some(ref token) = self.current_token {
             if token.token_type == TokenType::Newline {
@@ -85,40 +136,122 @@ impl Parser {
         self.skip_newlines();
         
         while let Some(ref token) = self.current_token {
-            if token.token_type == TokenType::EOF {
+            if token.token_type == TokenType::Eof {
                 break;
             }
             
-            if token.token_type == TokenType::Newline {
+            if Self::is_separator(token) {
                 self.advance();
                 continue;
             }
             
             let stmt = self.parse_statement()?;
-            statements.push(Box::new(stmt));
+            statements.push(stmt);
             
-            self.skip_newlines();
+            self.skip_separators();
         }
         
         Ok(ASTNode::new_program(statements))
     }
     
+    /// Like `parse`, but keeps going after a syntax error so every error in
+    /// the file can be reported at once.
+    pub fn parse_all(&mut self) -> Result<ASTNode, Vec<CompilerError>> {
+        let mut statements = Vec::new();
+        let mut errors = Vec::new();
+        
+        self.skip_newlines();
+        
+        while let Some(ref token) = self.current_token {
+            if token.token_type == TokenType::Eof {
+                break;
+            }
+            
+            if Self::is_separator(token) {
+                self.advance();
+                continue;
+            }
+            
+            let start = self.pos;
+            match self.parse_statement() {
+                Ok(stmt) => statements.push(stmt),
+                Err(message) => {
+                    let (line, column) = self.error_position();
+                    errors.push(CompilerError::parser(&message, line, column));
+                    self.recover(start);
+                    self.loop_depth = 0;
+                }
+            }
+            
+            self.skip_separators();
+        }
+        
+        if errors.is_empty() {
+            Ok(ASTNode::new_program(statements))
+        } else {
+            Err(errors)
+        }
+    }
+    
+    fn error_position(&self) -> (usize, usize) {
+        self.current_token.as_ref()
+            .or(self.tokens.last())
+            .map(|token| (token.line, token.column))
+            .unwrap_or((0, 0))
+    }
+    
+    // Skips the rest of the failed statement: up to the next newline outside
+    // any block the statement opened, so a broken function body is skipped whole
+    fn recover(&mut self, start: usize) {
+        let mut depth: i32 = 0;
+        for token in &self.tokens[start..self.pos.min(self.tokens.len())] {
+            match token.token_type {
+                TokenType::LBrace => depth += 1,
+                TokenType::RBrace => depth -= 1,
+                _ => {}
+            }
+        }
+        
+        while let Some(ref token) = self.current_token {
+            match token.token_type {
+                TokenType::Eof => break,
+                TokenType::Newline | TokenType::Semicolon if depth <= 0 => break,
+                TokenType::LBrace => depth += 1,
+                TokenType::RBrace => depth -= 1,
+                _ => {}
+            }
+            self.advance();
+        }
+    }
+    
     fn parse_statement(&mut self) -> Result<ASTNode, String> {
         if let Some(ref token) = self.current_token {
             match token.token_type {
                 TokenType::Keyword => {
                     match token.value.as_str() {
-                        "maanau" => self.parse_var_declaration(),
+                        "maanau" | "sthir" => self.parse_var_declaration(),
                         "yedi" => self.parse_if_statement(),
                         "jaba" => self.parse_while_loop(),
+                        "gara" => self.parse_do_while(),
                         "pratyek" => self.parse_for_each_loop(),
+                        "dohoryau" => self.parse_repeat_loop(),
+                        "chhan" => self.parse_chhan_statement(),
+                        "kosis" => self.parse_try_catch(),
+                        "phek" => self.parse_throw_statement(),
+                        "badha" | "ghata" => self.parse_step_statement(),
                         "kaam" => self.parse_function_declaration(),
+                        "birsau" => self.parse_delete_statement(),
                         "pathau" => self.parse_return_statement(),
                         "bhan" => self.parse_print_statement(),
+                        "likha" => self.parse_print_inline_statement(),
                         "rok" => self.parse_break_statement(),
                         "jane" => self.parse_continue_statement(),
                         "aayaat" => self.parse_import_statement(),
-                        _ => Err(format!("Unexpected keyword '{}' at line {}", token.value, token.line)),
+                        "aayaat_le" => self.parse_expression(),
+                        _ => Err(format!(
+                            "Unexpected keyword '{}' at line {}, column {}",
+                            token.value, token.line, token.column
+                        )),
                     }
                 }
                 TokenType::Identifier => {
@@ -145,7 +278,15 @@ impl Parser {
     }
     
     fn parse_var_declaration(&mut self) -> Result<ASTNode, String> {
-        self.expect_keyword("maanau")?;
+        // 'maanau' declares a variable, 'sthir' a constant
+        let is_const = matches!(&self.current_token, Some(token) if token.value == "sthir");
+        self.expect_keyword(if is_const { "sthir" } else { "maanau" })?;
+        
+        if let Some(ref token) = self.current_token {
+            if token.token_type == TokenType::LBracket || token.token_type == TokenType::LBrace {
+                return self.parse_destructuring(is_const);
+            }
+        }
         
         let name_token = self.expect(TokenType::Identifier)?;
         let name = name_token.value;
@@ -164,7 +305,43 @@ impl Parser {
         self.expect(TokenType::Operator)?; // expect '='
         let value = self.parse_expression()?;
         
-        Ok(ASTNode::new_var_declaration(name, type_hint, Box::new(value)))
+        Ok(ASTNode::new_var_declaration(name, type_hint, Box::new(value), is_const))
+    }
+    
+    // maanau [a, b] = list or maanau {x, y} = dict, after the keyword
+    fn parse_destructuring(&mut self, is_const: bool) -> Result<ASTNode, String> {
+        let from_dictionary = matches!(&self.current_token, Some(token) if token.token_type == TokenType::LBrace);
+        let closing = if from_dictionary { TokenType::RBrace } else { TokenType::RBracket };
+        self.advance(); // skip '[' or '{'
+        
+        let mut names: Vec<String> = Vec::new();
+        loop {
+            let name_token = self.expect(TokenType::Identifier)?;
+            if names.contains(&name_token.value) {
+                return Err(format!(
+                    "Destructuring ma '{}' dui patak aayo at line {}, column {}",
+                    name_token.value, name_token.line, name_token.column
+                ));
+            }
+            names.push(name_token.value);
+            
+            match &self.current_token {
+                Some(token) if token.token_type == TokenType::Comma => self.advance(),
+                _ => break,
+            }
+        }
+        self.expect(closing)?;
+        
+        self.expect(TokenType::Operator)?; // expect '='
+        let value = self.parse_expression()?;
+        
+        Ok(ASTNode::new_destructuring(names, from_dictionary, Box::new(value), is_const))
+    }
+    
+    fn parse_delete_statement(&mut self) -> Result<ASTNode, String> {
+        self.expect_keyword("birsau")?;
+        let name_token = self.expect(TokenType::Identifier)?;
+        Ok(ASTNode::Delete(name_token.value))
     }
     
     fn parse_assignment(&mut self) -> Result<ASTNode, String> {
@@ -187,7 +364,7 @@ impl Parser {
                 if let ASTNode::IndexAccess { object, index } = expr {
                     self.advance(); // skip '='
                     let value = self.parse_expression()?;
-                    return Ok(ASTNode::IndexAssignment { object, index, value: Box::new(value) });
+                    return Ok(ASTNode::new_index_assignment(object, index, Box::new(value)));
                 } else {
                     return Err("Invalid left-hand side in assignment".to_string());
                 }
@@ -200,7 +377,7 @@ impl Parser {
     fn parse_if_statement(&mut self) -> Result<ASTNode, String> {
         self.expect_keyword("yedi")?;
         
-        let condition = self.parse_expression()?;
+        let condition = self.parse_condition()?;
         
         self.expect_keyword("bhane")?;
         self.expect(TokenType::LBrace)?;
@@ -213,15 +390,15 @@ impl Parser {
                 break;
             }
             
-            if token.token_type == TokenType::Newline {
+            if Self::is_separator(token) {
                 self.advance();
                 continue;
             }
             
             let stmt = self.parse_statement()?;
-            then_block.push(Box::new(stmt));
+            then_block.push(stmt);
             
-            self.skip_newlines();
+            self.skip_separators();
         }
         
         self.expect(TokenType::RBrace)?;
@@ -242,15 +419,15 @@ impl Parser {
                         break;
                     }
                     
-                    if token.token_type == TokenType::Newline {
+                    if Self::is_separator(token) {
                         self.advance();
                         continue;
                     }
                     
                     let stmt = self.parse_statement()?;
-                    else_statements.push(Box::new(stmt));
+                    else_statements.push(stmt);
                     
-                    self.skip_newlines();
+                    self.skip_separators();
                 }
                 
                 self.expect(TokenType::RBrace)?;
@@ -265,44 +442,67 @@ impl Parser {
         ))
     }
     
+    // A condition for yedi or jaba samma; a lone '=' after it is almost
+    // always a typo for '=='
+    fn parse_condition(&mut self) -> Result<ASTNode, String> {
+        let condition = self.parse_expression()?;
+        if let Some(ref token) = self.current_token {
+            if token.token_type == TokenType::Operator && token.value == "=" {
+                return Err(format!(
+                    "Shayad tapai `==` bhanna khojdai hunuhunthyo? at line {}, column {}",
+                    token.line, token.column
+                ));
+            }
+        }
+        Ok(condition)
+    }
+    
     fn parse_while_loop(&mut self) -> Result<ASTNode, String> {
         self.expect_keyword("jaba")?;
         self.expect_keyword("samma")?;
         
-        let condition = self.parse_expression()?;
+        let condition = self.parse_condition()?;
+        let body = self.parse_loop_body()?;
         
-        self.expect(TokenType::LBrace)?;
+        Ok(ASTNode::new_while_loop(Box::new(condition), body))
+    }
+    
+    fn parse_do_while(&mut self) -> Result<ASTNode, String> {
+        self.expect_keyword("gara")?;
         
-        let mut body = Vec::new();
-        self.skip_newlines();
+        let body = self.parse_loop_body()?;
         
-        while let Some(ref token) = self.current_token {
-            if token.token_type == TokenType::RBrace {
-                break;
-            }
-            
-            if token.token_type == TokenType::Newline {
-                self.advance();
-                continue;
-            }
-            
-            let stmt = self.parse_statement()?;
-            body.push(Box::new(stmt));
-            
-            self.skip_newlines();
-        }
+        // The condition follows the closing brace: gara { ... } jaba samma cond
+        self.expect_keyword("jaba")?;
+        self.expect_keyword("samma")?;
         
-        self.expect(TokenType::RBrace)?;
+        let condition = self.parse_condition()?;
         
-        Ok(ASTNode::new_while_loop(Box::new(condition), body))
+        Ok(ASTNode::new_do_while_loop(body, Box::new(condition)))
     }
     
     fn parse_for_each_loop(&mut self) -> Result<ASTNode, String> {
         self.expect_keyword("pratyek")?;
         
-        // Get the loop variable name
+        // Get the loop variable name; with two, pratyek i, x ma list, the
+        // first gets the index (or the key of a dictionary)
         let var_token = self.expect(TokenType::Identifier)?;
-        let variable = var_token.value;
+        let mut index = None;
+        let mut variable = var_token.value;
+        if let Some(ref token) = self.current_token {
+            if token.token_type == TokenType::Comma {
+                self.advance(); // skip ','
+                let item_token = self.expect(TokenType::Identifier)?;
+                if item_token.value == variable {
+                    return Err(format!(
+                        "pratyek ma '{}' dui patak aayo at line {}, column {}",
+                        item_token.value, item_token.line, item_token.column
+                    ));
+                }
+                index = Some(variable);
+                variable = item_token.value;
+            }
+        }
         
         // Expect 'ma' keyword
         self.expect_keyword("ma")?;
@@ -310,31 +510,9 @@ impl Parser {
         // Parse the iterable expression
         let iterable = self.parse_expression()?;
         
-        // Parse the loop body
-        self.expect(TokenType::LBrace)?;
-        
-        let mut body = Vec::new();
-        self.skip_newlines();
-        
-        while let Some(ref token) = self.current_token {
-            if token.token_type == TokenType::RBrace {
-                break;
-            }
-            
-            if token.token_type == TokenType::Newline {
-                self.advance();
-                continue;
-            }
-            
-            let stmt = self.parse_statement()?;
-            body.push(Box::new(stmt));
-            
-            self.skip_newlines();
-        }
-        
-        self.expect(TokenType::RBrace)?;
+        let body = self.parse_loop_body()?;
         
-        Ok(ASTNode::new_for_each_loop(variable, Box::new(iterable), body))
+        Ok(ASTNode::new_for_each_loop(index, variable, Box::new(iterable), body))
     }
     
     fn parse_function_declaration(&mut self) -> Result<ASTNode, String> {
@@ -346,13 +524,52 @@ impl Parser {
         self.expect(TokenType::LParen)?;
         
         let mut parameters = Vec::new();
+        let mut has_default = false;
+        let mut is_variadic = false;
         
         // Parse parameter list
         if let Some(ref token) = self.current_token {
             if token.token_type != TokenType::RParen {
                 loop {
+                    // Rest parameter: kaam jamma(...nums) must come last
+                    if let Some(ref token) = self.current_token {
+                        if token.token_type == TokenType::Ellipsis {
+                            self.advance(); // skip '...'
+                            let rest_token = self.expect(TokenType::Identifier)?;
+                            Self::check_duplicate_parameter(&parameters, &rest_token)?;
+                            parameters.push((rest_token.value, None));
+                            is_variadic = true;
+                            
+                            if let Some(ref token) = self.current_token {
+                                if token.token_type != TokenType::RParen {
+                                    return Err(format!(
+                                        "Rest parameter '...' antim parameter hunu parcha at line {}",
+                                        rest_token.line
+                                    ));
+                                }
+                            }
+                            break;
+                        }
+                    }
+                    
                     let param_token = self.expect(TokenType::Identifier)?;
-                    parameters.push(param_token.value);
+                    Self::check_duplicate_parameter(&parameters, &param_token)?;
+                    
+                    // Optional default value: kaam greet(naam, greeting = "Namaste")
+                    let mut default = None;
+                    if let Some(ref token) = self.current_token {
+                        if token.token_type == TokenType::Operator && token.value == "=" {
+                            self.advance(); // skip '='
+                            default = Some(Box::new(self.parse_expression()?));
+                        } else if has_default {
+                            return Err(format!(
+                                "Parameter '{}' lai default value chahincha kinabhane agadi ko parameter ma default cha at line {}",
+                                param_token.value, param_token.line
+                            ));
+                        }
+                    }
+                    has_default = has_default || default.is_some();
+                    parameters.push((param_token.value, default));
                     
                     if let Some(ref token) = self.current_token {
                         if token.token_type == TokenType::Comma {
@@ -368,10 +585,89 @@ impl Parser {
         }
         
         self.expect(TokenType::RParen)?;
+        
+        // A loop around the kaam doesn't count: rok can't leave it from inside
+        let outer_loop_depth = mem::replace(&mut self.loop_depth, 0);
+        let body = self.parse_block();
+        self.loop_depth = outer_loop_depth;
+        let body = body?;
+        
+        Ok(ASTNode::new_function_declaration(name, parameters, body, is_variadic))
+    }
+    
+    // kaam f(a, a) would leave the first `a` unreachable inside the body
+    fn check_duplicate_parameter(parameters: &[(String, Option<Box<ASTNode>>)], token: &Token) -> Result<(), String> {
+        if parameters.iter().any(|(name, _)| *name == token.value) {
+            return Err(format!(
+                "Parameter '{}' dui patak aayo at line {}, column {}",
+                token.value, token.line, token.column
+            ));
+        }
+        Ok(())
+    }
+    
+    fn parse_return_statement(&mut self) -> Result<ASTNode, String> {
+        self.expect_keyword("pathau")?;
+        
+        // A bare `pathau` returns null, e.g. yedi hoina ready bhane { pathau }
+        if self.at_statement_end() {
+            return Ok(ASTNode::Return(Box::new(ASTNode::Null)));
+        }
+        let expr = self.parse_expression()?;
+        Ok(ASTNode::Return(Box::new(expr)))
+    }
+    
+    // Whether the current statement ends here: at a newline, ';', '}' or end of input
+    fn at_statement_end(&self) -> bool {
+        match self.current_token {
+            Some(ref token) => matches!(
+                token.token_type,
+                TokenType::Newline | TokenType::Semicolon | TokenType::Eof | TokenType::RBrace
+            ),
+            None => true,
+        }
+    }
+    
+    // badha x / ghata x add 1 / subtract 1; the interpreter checks x is a Number
+    fn parse_step_statement(&mut self) -> Result<ASTNode, String> {
+        let increment = matches!(&self.current_token, Some(token) if token.value == "badha");
+        self.advance(); // skip 'badha' or 'ghata'
+        let name = self.expect(TokenType::Identifier)?.value;
+        Ok(ASTNode::new_step(name, if increment { 1.0 } else { -1.0 }))
+    }
+    
+    fn parse_throw_statement(&mut self) -> Result<ASTNode, String> {
+        self.expect_keyword("phek")?;
+        let expr = self.parse_expression()?;
+        Ok(ASTNode::Throw(Box::new(expr)))
+    }
+    
+    // dohoryau 3 { ... } runs the block a fixed number of times
+    fn parse_repeat_loop(&mut self) -> Result<ASTNode, String> {
+        self.expect_keyword("dohoryau")?;
+        let count = self.parse_expression()?;
+        let body = self.parse_loop_body()?;
+        Ok(ASTNode::new_repeat_loop(Box::new(count), body))
+    }
+    
+    // kosis { ... } samat e { ... }
+    fn parse_try_catch(&mut self) -> Result<ASTNode, String> {
+        self.expect_keyword("kosis")?;
+        let try_block = self.parse_block()?;
+        self.expect_keyword("samat")?;
+        let error_name = self.expect(TokenType::Identifier)?.value;
+        let catch_block = self.parse_block()?;
+        Ok(ASTNode::new_try_catch(try_block, error_name, catch_block))
+    }
+    
+    // chhan x { 1 bhane { ... } 2 bhane { ... } natra { ... } }
+    fn parse_chhan_statement(&mut self) -> Result<ASTNode, String> {
+        self.expect_keyword("chhan")?;
+        let subject = self.parse_expression()?;
         self.expect(TokenType::LBrace)?;
         
-        let mut body = Vec::new();
-        self.skip_newlines();
+        let mut arms = Vec::new();
+        let mut default = None;
         
         while let Some(ref token) = self.current_token {
             if token.token_type == TokenType::RBrace {
@@ -383,46 +679,129 @@ impl Parser {
                 continue;
             }
             
-            let stmt = self.parse_statement()?;
-            body.push(Box::new(stmt));
+            if default.is_some() {
+                return Err(format!(
+                    "chhan ma 'natra' antim arm hunu parcha at line {}, column {}",
+                    token.line, token.column
+                ));
+            }
             
-            self.skip_newlines();
+            if token.token_type == TokenType::Keyword && token.value == "natra" {
+                self.advance(); // skip 'natra'
+                default = Some(self.parse_block()?);
+            } else {
+                let value = self.parse_expression()?;
+                self.expect_keyword("bhane")?;
+                let block = self.parse_block()?;
+                arms.push((Box::new(value), block));
+            }
         }
         
         self.expect(TokenType::RBrace)?;
-        
-        Ok(ASTNode::new_function_declaration(name, parameters, body))
-    }
-    
-    fn parse_return_statement(&mut self) -> Result<ASTNode, String> {
-        self.expect_keyword("pathau")?;
-        let expr = self.parse_expression()?;
-        Ok(ASTNode::Return(Box::new(expr)))
+        Ok(ASTNode::new_chhan_statement(Box::new(subject), arms, default))
     }
     
     fn parse_print_statement(&mut self) -> Result<ASTNode, String> {
         self.expect_keyword("bhan")?;
+        
+        // A bare `bhan` at the end of a line or block prints an empty line
+        let mut values = Vec::new();
+        if !self.at_statement_end() {
+            values.push(self.parse_expression()?);
+            while let Some(ref token) = self.current_token {
+                if token.token_type != TokenType::Comma {
+                    break;
+                }
+                self.advance(); // skip ','
+                values.push(self.parse_expression()?);
+            }
+        }
+        
+        Ok(ASTNode::Print(values))
+    }
+    
+    fn parse_print_inline_statement(&mut self) -> Result<ASTNode, String> {
+        self.expect_keyword("likha")?;
         let expr = self.parse_expression()?;
-        Ok(ASTNode::Print(Box::new(expr)))
+        Ok(ASTNode::PrintInline(Box::new(expr)))
     }
     
     fn parse_break_statement(&mut self) -> Result<ASTNode, String> {
-        self.expect_keyword("rok")?;
+        self.expect_inside_loop("rok")?;
         Ok(ASTNode::Break)
     }
     
     fn parse_continue_statement(&mut self) -> Result<ASTNode, String> {
-        self.expect_keyword("jane")?;
+        self.expect_inside_loop("jane")?;
         Ok(ASTNode::Continue)
     }
     
+    // rok/jane outside a loop would only fail once the program reached them
+    fn expect_inside_loop(&mut self, keyword: &str) -> Result<(), String> {
+        let token = self.expect_keyword(keyword)?;
+        if self.loop_depth == 0 {
+            return Err(format!(
+                "'{}' loop bhitra matra prayog garna milcha at line {}, column {}",
+                keyword, token.line, token.column
+            ));
+        }
+        Ok(())
+    }
+    
     fn parse_import_statement(&mut self) -> Result<ASTNode, String> {
         self.expect_keyword("aayaat")?;
         
-        let filename_token = self.expect(TokenType::String)?;
-        let filename = filename_token.value;
+        let filename = self.expect_module_name()?;
+        
+        // Optional namespace: aayaat "math.nep" naam ma ganit
+        // 'naam' isn't a keyword so it stays usable as a variable name elsewhere
+        let mut alias = None;
+        if let Some(ref token) = self.current_token {
+            if token.token_type == TokenType::Identifier && token.value == "naam" {
+                self.advance(); // skip 'naam'
+                self.expect_keyword("ma")?;
+                alias = Some(self.expect(TokenType::Identifier)?.value);
+            }
+        }
+        
+        Ok(ASTNode::new_import(filename, alias))
+    }
+    
+    // "file.nep" for a file, or a bare name like ganit for a prelude module
+    fn expect_module_name(&mut self) -> Result<String, String> {
+        match self.current_token {
+            Some(ref token) if token.token_type == TokenType::Identifier => {
+                Ok(self.expect(TokenType::Identifier)?.value)
+            }
+            _ => Ok(self.expect(TokenType::String)?.value),
+        }
+    }
+    
+    // Parses call arguments after '(' up to and including ')'
+    fn parse_call_arguments(&mut self) -> Result<Vec<ASTNode>, String> {
+        let mut arguments = Vec::new();
+        
+        if let Some(ref token) = self.current_token {
+            if token.token_type != TokenType::RParen {
+                loop {
+                    let arg = self.parse_expression()?;
+                    arguments.push(arg);
+                    
+                    if let Some(ref token) = self.current_token {
+                        if token.token_type == TokenType::Comma {
+                            self.advance();
+                        } else {
+                            break;
+                        }
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
         
-        Ok(ASTNode::new_import(filename))
+        self.expect(TokenType::RParen)?;
+        Ok(arguments)
     }
     
     fn parse_expression(&mut self) -> Result<ASTNode, String> {
@@ -447,13 +826,13 @@ impl Parser {
     }
     
     fn parse_logical_and(&mut self) -> Result<ASTNode, String> {
-        let mut left = self.parse_comparison()?;
+        let mut left = self.parse_logical_not()?;
         
         while let Some(ref token) = self.current_token {
             if token.token_type == TokenType::Keyword && token.value == "ra" {
                 let operator = token.value.clone();
                 self.advance();
-                let right = self.parse_comparison()?;
+                let right = self.parse_logical_not()?;
                 left = ASTNode::new_binary_op(Box::new(left), operator, Box::new(right));
             } else {
                 break;
@@ -463,13 +842,82 @@ impl Parser {
         Ok(left)
     }
     
+    // hoina binds looser than comparison, so hoina a == b is hoina (a == b)
+    fn parse_logical_not(&mut self) -> Result<ASTNode, String> {
+        match self.current_token {
+            Some(ref token) if token.token_type == TokenType::Keyword && token.value == "hoina" => {
+                let operator = token.value.clone();
+                self.advance();
+                let operand = self.parse_logical_not()?;
+                Ok(ASTNode::new_unary_op(operator, Box::new(operand)))
+            }
+            _ => self.parse_comparison(),
+        }
+    }
+    
     fn parse_comparison(&mut self) -> Result<ASTNode, String> {
-        let mut left = self.parse_addition()?;
+        let mut left = self.parse_bitwise()?;
+        let mut previous: Option<String> = None;
         
         while let Some(ref token) = self.current_token {
             if token.token_type == TokenType::Operator {
                 match token.value.as_str() {
                     "==" | "!=" | ">" | "<" | ">=" | "<=" => {
+                        let operator = token.value.clone();
+                        // (1 < x) < 10 would compare a boolean with a number, which always fails
+                        if let Some(first) = previous.as_deref().filter(|op| is_ordering(op)) {
+                            if is_ordering(&operator) {
+                                return Err(format!(
+                                    "Comparison jodna mildaina: 'a {0} b {1} c' ko satta 'a {0} b ra b {1} c' lekha at line {2}, column {3}",
+                                    first, operator, token.line, token.column
+                                ));
+                            }
+                        }
+                        previous = Some(operator.clone());
+                        self.advance();
+                        let right = self.parse_bitwise()?;
+                        left = ASTNode::new_binary_op(Box::new(left), operator, Box::new(right));
+                    }
+                    _ => break,
+                }
+            } else {
+                break;
+            }
+        }
+        
+        Ok(left)
+    }
+    
+    // Bitwise operators bind tighter than comparisons, so `x & 1 == 0` is `(x & 1) == 0`
+    fn parse_bitwise(&mut self) -> Result<ASTNode, String> {
+        let mut left = self.parse_shift()?;
+        
+        while let Some(ref token) = self.current_token {
+            if token.token_type == TokenType::Operator {
+                match token.value.as_str() {
+                    "&" | "|" | "^" => {
+                        let operator = token.value.clone();
+                        self.advance();
+                        let right = self.parse_shift()?;
+                        left = ASTNode::new_binary_op(Box::new(left), operator, Box::new(right));
+                    }
+                    _ => break,
+                }
+            } else {
+                break;
+            }
+        }
+        
+        Ok(left)
+    }
+    
+    fn parse_shift(&mut self) -> Result<ASTNode, String> {
+        let mut left = self.parse_addition()?;
+        
+        while let Some(ref token) = self.current_token {
+            if token.token_type == TokenType::Operator {
+                match token.value.as_str() {
+                    "<<" | ">>" => {
                         let operator = token.value.clone();
                         self.advance();
                         let right = self.parse_addition()?;
@@ -532,18 +980,14 @@ impl Parser {
     fn parse_unary(&mut self) -> Result<ASTNode, String> {
         if let Some(ref token) = self.current_token {
             match token.token_type {
-                TokenType::Keyword if token.value == "hoina" => {
-                    let operator = token.value.clone();
-                    self.advance();
-                    let operand = self.parse_unary()?;
-                    Ok(ASTNode::new_unary_op(operator, Box::new(operand)))
-                }
                 TokenType::Operator if token.value == "-" => {
                     let operator = token.value.clone();
                     self.advance();
                     let operand = self.parse_unary()?;
                     Ok(ASTNode::new_unary_op(operator, Box::new(operand)))
                 }
+                // hoina can still start an operand, as in galat == hoina x
+                TokenType::Keyword if token.value == "hoina" => self.parse_logical_not(),
                 _ => self.parse_primary(),
             }
         } else {
@@ -574,7 +1018,28 @@ impl Parser {
                             self.advance();
                             Ok(ASTNode::Boolean(false))
                         }
-                        _ => Err(format!("Unexpected keyword '{}' in expression", token.value)),
+                        "aayaat_le" => {
+                            self.advance();
+                            Ok(ASTNode::new_module_import(self.expect_module_name()?))
+                        }
+                        "aayaat" => {
+                            // maanau m = aayaat "lib.nep" binds the module's export value
+                            self.advance();
+                            Ok(ASTNode::new_import(self.expect_module_name()?, None))
+                        }
+                        "yedi" => {
+                            // Same syntax as the statement, but the taken branch yields a value
+                            match self.parse_if_statement()? {
+                                ASTNode::IfStatement { condition, then_block, else_block } => {
+                                    Ok(ASTNode::IfExpression { condition, then_block, else_block })
+                                }
+                                other => Ok(other),
+                            }
+                        }
+                        _ => Err(format!(
+                            "Unexpected keyword '{}' in expression at line {}, column {}",
+                            token.value, token.line, token.column
+                        )),
                     }
                 }
                 TokenType::Identifier => {
@@ -583,49 +1048,42 @@ impl Parser {
                     
                     let mut result = ASTNode::Identifier(name.clone());
                     
-                    // Handle function calls or indexing
-                    loop {
-                        if let Some(ref token) = self.current_token {
-                            if token.token_type == TokenType::LParen {
-                                // Function call - only valid for identifiers
-                                if let ASTNode::Identifier(func_name) = &result {
-                                    self.advance(); // skip '('
-                                    
-                                    let mut arguments = Vec::new();
-                                    
-                                    if let Some(ref token) = self.current_token {
-                                        if token.token_type != TokenType::RParen {
-                                            loop {
-                                                let arg = self.parse_expression()?;
-                                                arguments.push(Box::new(arg));
-                                                
-                                                if let Some(ref token) = self.current_token {
-                                                    if token.token_type == TokenType::Comma {
-                                                        self.advance();
-                                                    } else {
-                                                        break;
-                                                    }
-                                                } else {
-                                                    break;
-                                                }
-                                            }
-                                        }
-                                    }
-                                    
-                                    self.expect(TokenType::RParen)?;
-                                    result = ASTNode::new_function_call(func_name.clone(), arguments);
-                                } else {
-                                    return Err("Cannot call function on non-identifier".to_string());
+                    // Handle function calls, indexing or member access
+                    while let Some(ref token) = self.current_token {
+                        if token.token_type == TokenType::LParen {
+                            self.advance(); // skip '('
+                            let arguments = self.parse_call_arguments()?;
+                            
+                            // Calls by name keep their own node; anything else calls the value
+                            result = match result {
+                                ASTNode::Identifier(func_name) => {
+                                    ASTNode::new_function_call(func_name, arguments)
                                 }
-                            } else if token.token_type == TokenType::LBracket {
-                                // Index access
-                                self.advance(); // skip '['
-                                let index = self.parse_expression()?;
-                                self.expect(TokenType::RBracket)?;
-                                result = ASTNode::new_index_access(Box::new(result), Box::new(index));
+                                callee => ASTNode::new_expression_call(Box::new(callee), arguments),
+                            };
+                        } else if token.token_type == TokenType::Dot {
+                            // module.name(...) is a member to call; without the
+                            // parentheses, mylist.lambai or module.NAME is a property
+                            self.advance(); // skip '.'
+                            let member = self.expect(TokenType::Identifier)?;
+                            let is_call = matches!(&self.current_token, Some(token) if token.token_type == TokenType::LParen);
+                            result = if is_call {
+                                ASTNode::new_member_access(Box::new(result), member.value)
                             } else {
-                                break;
-                            }
+                                ASTNode::new_property_access(Box::new(result), member.value)
+                            };
+                        } else if token.token_type == TokenType::LBracket {
+                            // Index access
+                            self.advance(); // skip '['
+                            let index = self.parse_expression()?;
+                            self.expect(TokenType::RBracket)?;
+                            result = ASTNode::new_index_access(Box::new(result), Box::new(index));
+                        } else if token.token_type == TokenType::SafeLBracket {
+                            // Safe index access: dict?["key"]
+                            self.advance(); // skip '?['
+                            let index = self.parse_expression()?;
+                            self.expect(TokenType::RBracket)?;
+                            result = ASTNode::new_safe_index_access(Box::new(result), Box::new(index));
                         } else {
                             break;
                         }
@@ -644,7 +1102,7 @@ impl Parser {
                         if token.token_type != TokenType::RBracket {
                             loop {
                                 let element = self.parse_expression()?;
-                                elements.push(Box::new(element));
+                                elements.push(element);
                                 
                                 self.skip_newlines();
                                 
@@ -666,7 +1124,7 @@ impl Parser {
                     Ok(ASTNode::new_list_literal(elements))
                 }
                 TokenType::LBrace => {
-                    // Dictionary literal: {"key": value, "key2": value2}
+                    // Dictionary literal: {"key": value, 2: value2, sahi: value3}
                     self.advance(); // skip '{'
                     self.skip_newlines();
                     
@@ -675,15 +1133,14 @@ impl Parser {
                     if let Some(ref token) = self.current_token {
                         if token.token_type != TokenType::RBrace {
                             loop {
-                                // Parse key (must be string)
-                                let key_token = self.expect(TokenType::String)?;
-                                let key = key_token.value;
+                                // Keys are expressions; they must evaluate to a number, string or boolean
+                                let key = self.parse_expression()?;
                                 
                                 self.expect(TokenType::Colon)?;
                                 self.skip_newlines();
                                 
                                 let value = self.parse_expression()?;
-                                pairs.push((key, Box::new(value)));
+                                pairs.push((Box::new(key), Box::new(value)));
                                 
                                 self.skip_newlines();
                                 
@@ -710,10 +1167,24 @@ impl Parser {
                     self.expect(TokenType::RParen)?;
                     Ok(expr)
                 }
-                _ => Err(format!("Unexpected token {:?} in expression", token)),
+                _ => {
+                    // Newline and EOF have no readable text of their own
+                    let found = match token.token_type {
+                        TokenType::Newline | TokenType::Eof => format!("{:?}", token.token_type),
+                        _ => format!("{:?} '{}'", token.token_type, token.value),
+                    };
+                    Err(format!(
+                        "Unexpected token {} in expression at line {}, column {}",
+                        found, token.line, token.column
+                    ))
+                }
             }
         } else {
             Err("Unexpected end of input in expression".to_string())
         }
     }
-}
\ No newline at end of file
+}
+
+fn is_ordering(operator: &str) -> bool {
+    matches!(operator, "<" | ">" | "<=" | ">=")
+}
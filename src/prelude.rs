@@ -0,0 +1,26 @@
+use crate::value::Value;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// A Rust function callable from khukuri code once its module is imported.
+pub type NativeFunction = Rc<dyn Fn(&[Value]) -> Result<Value, String>>;
+
+/// A standard-library module that `aayaat name` loads by name instead of
+/// from a file.
+#[derive(Clone)]
+pub enum PreludeModule {
+    /// khukuri source, run like an imported file
+    Source(String),
+    /// Functions implemented in Rust, callable by name after the import
+    Native(HashMap<String, NativeFunction>),
+}
+
+// Modules every interpreter starts with
+pub(crate) fn standard_modules() -> HashMap<String, PreludeModule> {
+    let mut modules = HashMap::new();
+    modules.insert(
+        "ganit".to_string(),
+        PreludeModule::Source(include_str!("prelude/ganit.nep").to_string()),
+    );
+    modules
+}
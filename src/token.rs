@@ -9,7 +9,7 @@ pub enum TokenType {
     String,           // "text"
     
     // Operators
-    Operator,         // =, +, -, *, /, %, ==, !=, >, <, >=, <=
+    Operator,         // =, +, -, *, /, %, ==, !=, >, <, >=, <=, &, |, ^, <<, >>
     
     // Delimiters
     LBrace,           // {
@@ -17,13 +17,17 @@ pub enum TokenType {
     LParen,           // (
     RParen,           // )
     LBracket,         // [
+    SafeLBracket,     // ?[ (index that gives null instead of failing)
     RBracket,         // ]
     Comma,            // ,
     Colon,            // : (for optional type hints)
+    Ellipsis,         // ... (rest parameter)
+    Dot,              // . (member access)
     
     // Special
     Newline,          // \n
-    EOF,              // End of file
+    Semicolon,        // ; (separates statements on one line)
+    Eof,              // End of file
 }
 
 #[derive(Debug, Clone, PartialEq)]
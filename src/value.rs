@@ -1,4 +1,62 @@
+use crate::ast::ASTNode;
+use crate::environment::Environment;
+use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Function {
+    pub name: String,
+    pub parameters: Vec<(String, Option<Box<ASTNode>>)>, // (name, default value)
+    pub body: Vec<ASTNode>,
+    pub is_variadic: bool, // last parameter collects extra arguments into a list
+    pub module: Option<Rc<ModuleScope>>, // globals of the module it came from via aayaat_le
+    pub captured: Option<Rc<RefCell<HashMap<String, Value>>>>, // locals around a nested kaam, kept between calls
+}
+
+// A module's top-level variables and functions, captured once it finished
+// loading so its exported functions still see their neighbours when called
+pub struct ModuleScope {
+    pub environment: Environment,
+    pub functions: HashMap<String, Rc<Function>>,
+}
+
+impl fmt::Debug for ModuleScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ModuleScope({} functions)", self.functions.len())
+    }
+}
+
+// Two scopes are only the same if they came from the same import
+impl PartialEq for ModuleScope {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Function {
+    // Parameters bound one-to-one with arguments, i.e. all but a rest parameter
+    pub fn fixed_parameters(&self) -> &[(String, Option<Box<ASTNode>>)] {
+        if self.is_variadic {
+            &self.parameters[..self.parameters.len() - 1]
+        } else {
+            &self.parameters
+        }
+    }
+    
+    // Fixed parameters without a default value must always be passed
+    pub fn required_parameters(&self) -> usize {
+        self.fixed_parameters().iter().filter(|(_, default)| default.is_none()).count()
+    }
+    
+    pub fn accepts(&self, arg_count: usize) -> bool {
+        arg_count >= self.required_parameters()
+            && (self.is_variadic || arg_count <= self.parameters.len())
+    }
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value {
@@ -6,36 +64,115 @@ pub enum Value {
     String(String),
     Boolean(bool),
     List(Vec<Value>),
-    Dictionary(HashMap<String, Value>),
+    Dictionary(HashMap<HashableValue, Value>),
+    Function(Rc<Function>),
+    Range { start: f64, end: f64, step: f64 }, // lazy shrinkhala, end is exclusive
     Null,
 }
 
-impl Value {
-    pub fn to_string(&self) -> String {
+// The values that can be dictionary keys
+#[derive(Debug, Clone)]
+pub enum HashableValue {
+    Number(f64), // never NaN, and -0 is stored as 0
+    String(String),
+    Boolean(bool),
+}
+
+impl HashableValue {
+    pub fn from_value(value: &Value) -> Result<Self, String> {
+        match value {
+            Value::Number(n) if n.is_nan() => Err("NaN dictionary key huna sakdaina".to_string()),
+            // -0 == 0, so both must hash the same
+            Value::Number(n) => Ok(HashableValue::Number(if *n == 0.0 { 0.0 } else { *n })),
+            Value::String(s) => Ok(HashableValue::String(s.clone())),
+            Value::Boolean(b) => Ok(HashableValue::Boolean(*b)),
+            other => Err(format!("{} dictionary key huna sakdaina", other.get_type())),
+        }
+    }
+    
+    pub fn to_value(&self) -> Value {
         match self {
-            Value::Number(n) => {
-                if n.fract() == 0.0 {
-                    format!("{}", *n as i64)
-                } else {
-                    format!("{}", n)
-                }
-            }
-            Value::String(s) => s.clone(),
-            Value::Boolean(b) => if *b { "sahi" } else { "galat" }.to_string(),
-            Value::List(list) => {
-                let items: Vec<String> = list.iter().map(|v| v.to_string()).collect();
-                format!("[{}]", items.join(", "))
-            }
-            Value::Dictionary(dict) => {
-                let items: Vec<String> = dict.iter()
-                    .map(|(k, v)| format!("\"{}\": {}", k, v.to_string()))
-                    .collect();
-                format!("{{{}}}", items.join(", "))
-            }
-            Value::Null => "null".to_string(),
+            HashableValue::Number(n) => Value::Number(*n),
+            HashableValue::String(s) => Value::String(s.clone()),
+            HashableValue::Boolean(b) => Value::Boolean(*b),
         }
     }
     
+    // Keys in dictionary output: strings quoted, anything else as printed
+    pub(crate) fn repr(&self) -> String {
+        match self {
+            HashableValue::String(s) => format!("\"{}\"", s),
+            other => other.to_string(),
+        }
+    }
+}
+
+impl From<&str> for HashableValue {
+    fn from(key: &str) -> Self {
+        HashableValue::String(key.to_string())
+    }
+}
+
+impl PartialEq for HashableValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for HashableValue {}
+
+impl Hash for HashableValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            HashableValue::Number(n) => (0u8, n.to_bits()).hash(state),
+            HashableValue::String(s) => (1u8, s).hash(state),
+            HashableValue::Boolean(b) => (2u8, b).hash(state),
+        }
+    }
+}
+
+// Booleans, then numbers, then strings; used to list keys in a stable order
+impl Ord for HashableValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (HashableValue::Boolean(l), HashableValue::Boolean(r)) => l.cmp(r),
+            (HashableValue::Number(l), HashableValue::Number(r)) => l.total_cmp(r),
+            (HashableValue::String(l), HashableValue::String(r)) => l.cmp(r),
+            (HashableValue::Boolean(_), _) => Ordering::Less,
+            (_, HashableValue::Boolean(_)) => Ordering::Greater,
+            (HashableValue::Number(_), _) => Ordering::Less,
+            (_, HashableValue::Number(_)) => Ordering::Greater,
+        }
+    }
+}
+
+impl PartialOrd for HashableValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for HashableValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_value())
+    }
+}
+
+// Number of items a range yields without materializing it
+pub fn range_len(start: f64, end: f64, step: f64) -> usize {
+    let count = ((end - start) / step).ceil();
+    if count > 0.0 { count as usize } else { 0 }
+}
+
+impl Value {
+    // Lists and dictionaries own their elements, so a copy is independent all
+    // the way down and changing it can't affect the original. Functions are the
+    // exception: a copy shares the original's captured variables, so after
+    // `maanau c2 = c` calling either closure updates the state both see.
+    pub fn deep_clone(&self) -> Value {
+        self.clone()
+    }
+    
     pub fn is_truthy(&self) -> bool {
         match self {
             Value::Boolean(b) => *b,
@@ -44,6 +181,111 @@ impl Value {
             Value::String(s) => !s.is_empty(),
             Value::List(list) => !list.is_empty(),
             Value::Dictionary(dict) => !dict.is_empty(),
+            Value::Function(_) => true,
+            Value::Range { start, end, step } => range_len(*start, *end, *step) > 0,
+        }
+    }
+    
+    // Numbers order numerically and strings lexicographically; anything else is incomparable
+    pub fn compare(&self, other: &Value) -> Result<Ordering, String> {
+        match (self, other) {
+            (Value::Number(l), Value::Number(r)) => l.partial_cmp(r)
+                .ok_or_else(|| "NaN lai compare garna mildaina".to_string()),
+            (Value::String(l), Value::String(r)) => Ok(l.cmp(r)),
+            _ => Err(format!(
+                "{} ra {} compare garna mildaina",
+                self.get_type(), other.get_type()
+            )),
+        }
+    }
+    
+    // Type with container contents, e.g. "List<Number>" or "Dictionary<String, List<mixed>>"
+    pub fn describe_shape(&self) -> String {
+        match self {
+            Value::List(list) => format!("List<{}>", Self::common_shape(list.iter())),
+            Value::Dictionary(dict) => {
+                let keys: Vec<Value> = dict.keys().map(HashableValue::to_value).collect();
+                format!(
+                    "Dictionary<{}, {}>",
+                    Self::common_shape(keys.iter()),
+                    Self::common_shape(dict.values())
+                )
+            }
+            other => other.get_type().to_string(),
+        }
+    }
+    
+    // The shape shared by all values, "mixed" if they differ, "empty" if there are none
+    fn common_shape<'a>(values: impl Iterator<Item = &'a Value>) -> String {
+        let mut common: Option<String> = None;
+        for value in values {
+            let shape = value.describe_shape();
+            match &common {
+                None => common = Some(shape),
+                Some(existing) if *existing != shape => return "mixed".to_string(),
+                Some(_) => {}
+            }
+        }
+        common.unwrap_or_else(|| "empty".to_string())
+    }
+    
+    // Copy with every number, including those inside lists and dictionaries,
+    // rounded to `decimals` places; printing then drops the trailing zeros
+    pub fn rounded(&self, decimals: usize) -> Value {
+        match self {
+            Value::Number(n) if n.is_finite() && n.fract() != 0.0 => {
+                Value::Number(format!("{:.*}", decimals, n).parse().unwrap_or(*n))
+            }
+            Value::List(list) => Value::List(list.iter().map(|item| item.rounded(decimals)).collect()),
+            Value::Dictionary(dict) => Value::Dictionary(
+                dict.iter().map(|(key, item)| (key.clone(), item.rounded(decimals))).collect()
+            ),
+            other => other.clone(),
+        }
+    }
+    
+    // Multi-line rendering of nested lists and dictionaries, indented two
+    // spaces per level; dictionary keys are sorted so the output is stable
+    pub fn pretty(&self) -> String {
+        let mut out = String::new();
+        self.write_pretty(&mut out, 0);
+        out
+    }
+    
+    fn write_pretty(&self, out: &mut String, level: usize) {
+        let indent = "  ".repeat(level + 1);
+        match self {
+            Value::List(list) if !list.is_empty() => {
+                out.push_str("[\n");
+                for (i, item) in list.iter().enumerate() {
+                    out.push_str(&indent);
+                    item.write_pretty_item(out, level + 1);
+                    out.push_str(if i + 1 < list.len() { ",\n" } else { "\n" });
+                }
+                out.push_str(&"  ".repeat(level));
+                out.push(']');
+            }
+            Value::Dictionary(dict) if !dict.is_empty() => {
+                let mut entries: Vec<_> = dict.iter().collect();
+                entries.sort_by_key(|(key, _)| *key);
+                out.push_str("{\n");
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    out.push_str(&format!("{}{}: ", indent, key.repr()));
+                    value.write_pretty_item(out, level + 1);
+                    out.push_str(if i + 1 < entries.len() { ",\n" } else { "\n" });
+                }
+                out.push_str(&"  ".repeat(level));
+                out.push('}');
+            }
+            other => out.push_str(&other.to_string()),
+        }
+    }
+    
+    // Inside a container strings are quoted, like dictionary keys
+    fn write_pretty_item(&self, out: &mut String, level: usize) {
+        match self {
+            Value::String(s) => out.push_str(&format!("\"{}\"", s)),
+            other => other.write_pretty(out, level),
         }
     }
     
@@ -54,7 +296,41 @@ impl Value {
             Value::Boolean(_) => "Boolean",
             Value::List(_) => "List",
             Value::Dictionary(_) => "Dictionary",
+            Value::Function(_) => "Function",
+            Value::Range { .. } => "Range",
             Value::Null => "Null",
         }
     }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Number(n) => {
+                // Past 2^53 the cast to i64 can saturate, so let f64 format those itself
+                if n.fract() == 0.0 && n.abs() <= 9007199254740992.0 {
+                    write!(f, "{}", *n as i64)
+                } else {
+                    write!(f, "{}", n)
+                }
+            }
+            Value::String(s) => write!(f, "{}", s),
+            Value::Boolean(b) => write!(f, "{}", if *b { "sahi" } else { "galat" }),
+            Value::List(list) => {
+                let items: Vec<String> = list.iter().map(|v| v.to_string()).collect();
+                write!(f, "[{}]", items.join(", "))
+            }
+            Value::Dictionary(dict) => {
+                let items: Vec<String> = dict.iter()
+                    .map(|(k, v)| format!("{}: {}", k.repr(), v))
+                    .collect();
+                write!(f, "{{{}}}", items.join(", "))
+            }
+            Value::Function(function) => write!(f, "<kaam {}>", function.name),
+            Value::Range { start, end, step } => {
+                write!(f, "shrinkhala({}, {}, {})", Value::Number(*start), Value::Number(*end), Value::Number(*step))
+            }
+            Value::Null => write!(f, "null"),
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,29 @@
+use khukuri::interpreter::Interpreter;
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+// A writer the test keeps a handle to after giving it to the interpreter
+#[derive(Clone, Default)]
+struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// Runs a program in a fresh interpreter, returning everything it printed or
+// the error it stopped with
+pub fn run(source: &str) -> Result<String, String> {
+    let buffer = SharedBuffer::default();
+    let mut interpreter = Interpreter::with_writer(Box::new(buffer.clone()));
+    interpreter.eval_source(source)?;
+    let printed = buffer.0.borrow().clone();
+    Ok(String::from_utf8(printed).unwrap())
+}
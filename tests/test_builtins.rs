@@ -0,0 +1,608 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn test_dict_from_pairs_round_trip() {
+    let output = run(r#"
+maanau d = {"naam": "Ram", "umar": 25, "shahar": "Pokhara"}
+bhan dict_from_pairs(entries(d)) == d
+"#).unwrap();
+    assert_eq!(output, "sahi\n");
+}
+
+#[test]
+fn test_dict_from_pairs_later_pairs_win() {
+    let output = run(r#"bhan dict_from_pairs([["a", 1], ["a", 2]])["a"]"#).unwrap();
+    assert_eq!(output, "2\n");
+}
+
+#[test]
+fn test_dict_from_pairs_rejects_malformed_pairs() {
+    let error = run(r#"dict_from_pairs([["a", 1], ["b"]])"#).unwrap_err();
+    assert_eq!(error, "dict_from_pairs: element 1 [key, value] pair hoina: [b]");
+}
+
+#[test]
+fn test_tulana_numbers() {
+    let output = run("bhan tulana(1, 2)\nbhan tulana(2, 2)\nbhan tulana(3, 2)").unwrap();
+    assert_eq!(output, "-1\n0\n1\n");
+}
+
+#[test]
+fn test_tulana_strings() {
+    let output = run(r#"
+bhan tulana("a", "b")
+bhan tulana("b", "b")
+bhan tulana("c", "b")
+"#).unwrap();
+    assert_eq!(output, "-1\n0\n1\n");
+}
+
+#[test]
+fn test_tulana_incomparable_types() {
+    let error = run(r#"tulana(1, "b")"#).unwrap_err();
+    assert_eq!(error, "Number ra String compare garna mildaina");
+}
+
+#[test]
+fn test_kram_with_descending() {
+    let output = run(r#"
+kaam ulto(a, b) {
+    pathau tulana(b, a)
+}
+bhan kram_with([3, 1, 4, 1, 5], ulto)
+"#).unwrap();
+    assert_eq!(output, "[5, 4, 3, 1, 1]\n");
+}
+
+#[test]
+fn test_kram_with_is_stable() {
+    let output = run(r#"
+kaam umar_le(a, b) {
+    pathau tulana(a["umar"], b["umar"])
+}
+maanau log = [{"naam": "a", "umar": 2}, {"naam": "b", "umar": 1}, {"naam": "c", "umar": 2}, {"naam": "d", "umar": 1}]
+pratyek p ma kram_with(log, umar_le) {
+    likha p["naam"]
+}
+"#).unwrap();
+    assert_eq!(output, "bdac");
+}
+
+#[test]
+fn test_kram_with_rejects_bad_comparators() {
+    let error = run("kaam ek(a) { pathau 0 }\nkram_with([1, 2], ek)").unwrap_err();
+    assert_eq!(error, "kram_with ko comparator le 2 arguments linu parcha, ek le 1 lincha");
+    
+    let error = run("kaam s(a, b) { pathau \"x\" }\nkram_with([1, 2], s)").unwrap_err();
+    assert_eq!(error, "kram_with ko comparator le Number pathaunu parcha, String payo");
+}
+
+#[test]
+fn test_math_builtins() {
+    let output = run(r#"
+bhan niramek(-3.5)
+bhan tala(2.7)
+bhan mathi(2.1)
+bhan najik(2.5)
+bhan bargamul(16)
+"#).unwrap();
+    assert_eq!(output, "3.5\n2\n3\n3\n4\n");
+}
+
+#[test]
+fn test_bargamul_of_negative_number() {
+    let error = run("bargamul(-1)").unwrap_err();
+    assert_eq!(error, "Negative number ko bargamul hudaina: -1");
+}
+
+#[test]
+fn test_math_builtins_need_numbers() {
+    let error = run(r#"tala("x")"#).unwrap_err();
+    assert_eq!(error, "tala lai Number chahincha, String payo");
+}
+
+#[test]
+fn test_koshis_num() {
+    let output = run(r#"
+bhan koshis_num("42")
+bhan koshis_num("abc")
+bhan koshis_num(7)
+"#).unwrap();
+    assert_eq!(output, "42\nnull\n7\n");
+}
+
+#[test]
+fn test_bich_halne() {
+    let output = run(r#"bhan bich_halne([1, 2, 3], 0)"#).unwrap();
+    assert_eq!(output, "[1, 0, 2, 0, 3]\n");
+}
+
+#[test]
+fn test_bich_halne_short_lists() {
+    let output = run("bhan bich_halne([1], 0)\nbhan bich_halne([], 0)").unwrap();
+    assert_eq!(output, "[1]\n[]\n");
+}
+
+#[test]
+fn test_query_string() {
+    let output = run(r#"bhan query_string({"b": "hello world&more", "a": 1})"#).unwrap();
+    assert_eq!(output, "a=1&b=hello%20world%26more\n");
+}
+
+#[test]
+fn test_barabar_anadar() {
+    let output = run(r#"
+bhan barabar_anadar("Hello", "hello")
+bhan barabar_anadar("Hello", "help")
+"#).unwrap();
+    assert_eq!(output, "sahi\ngalat\n");
+}
+
+#[test]
+fn test_khoj_anadar() {
+    let output = run(r#"
+bhan khoj_anadar("hello world", "WORLD")
+bhan khoj_anadar("hello", "xyz")
+"#).unwrap();
+    assert_eq!(output, "6\n-1\n");
+}
+
+#[test]
+fn test_khoj_anadar_indexes_original_string() {
+    // İ lowercases to two chars, which mustn't shift the index
+    let output = run(r#"
+bhan khoj_anadar("İab", "B")
+bhan khoj_anadar("İab", "i")
+bhan khoj_anadar("नमस्ते World", "WORLD")
+bhan khoj_anadar("ΟΔΟΣ", "οδοσ")
+"#).unwrap();
+    assert_eq!(output, "2\n0\n7\n0\n");
+}
+
+#[test]
+fn test_akaar_varnan() {
+    let output = run(r#"
+bhan akaar_varnan([1, 2])
+bhan akaar_varnan([1, "a"])
+bhan akaar_varnan({"a": [1, 2], "b": [3]})
+"#).unwrap();
+    assert_eq!(output, "List<Number>\nList<mixed>\nDictionary<String, List<Number>>\n");
+}
+
+#[test]
+fn test_ghumau_and_kram_return_copies() {
+    let output = run(r#"
+maanau l = [3, 1, 2]
+bhan ghumau(l)
+bhan kram(l)
+bhan l
+"#).unwrap();
+    assert_eq!(output, "[2, 1, 3]\n[1, 2, 3]\n[3, 1, 2]\n");
+}
+
+#[test]
+fn test_kram_strings() {
+    let output = run(r#"bhan kram(["b", "c", "a"])"#).unwrap();
+    assert_eq!(output, "[a, b, c]\n");
+}
+
+#[test]
+fn test_kram_mixed_types() {
+    let error = run(r#"kram([1, "a"])"#).unwrap_err();
+    assert_eq!(error, "kram: list ma mixed types cha (Number ra String), sort garna mildaina");
+}
+
+#[test]
+fn test_thulo_and_sano() {
+    let output = run(r#"
+bhan thulo("Namaste")
+bhan sano("NaMaStE")
+"#).unwrap();
+    assert_eq!(output, "NAMASTE\nnamaste\n");
+}
+
+#[test]
+fn test_thulo_devanagari_unchanged() {
+    let output = run(r#"bhan thulo("नमस्ते")"#).unwrap();
+    assert_eq!(output, "नमस्ते\n");
+}
+
+#[test]
+fn test_suru_and_antya_huncha() {
+    let output = run(r#"
+bhan suru_huncha("namaste", "nam")
+bhan antya_huncha("namaste", "xyz")
+"#).unwrap();
+    assert_eq!(output, "sahi\ngalat\n");
+    
+    let error = run("thulo(5)").unwrap_err();
+    assert_eq!(error, "thulo lai String chahincha, Number payo");
+}
+
+#[test]
+fn test_jhyau_single_value() {
+    let output = run(r#"
+maanau sabai = sahi
+dohoryau 100 {
+    yedi jhyau(5, 5) != 5 bhane { sabai = galat }
+}
+bhan sabai
+"#).unwrap();
+    assert_eq!(output, "sahi\n");
+}
+
+#[test]
+fn test_jhyau_stays_in_range() {
+    let output = run(r#"
+maanau bahira = 0
+dohoryau 200 {
+    maanau n = jhyau(-1, 1)
+    yedi n < -1 wa n > 1 wa tala(n) != n bhane { bahira = bahira + 1 }
+}
+bhan bahira
+"#).unwrap();
+    assert_eq!(output, "0\n");
+}
+
+#[test]
+fn test_jhyau_rejects_bad_ranges() {
+    let error = run("jhyau(6, 5)").unwrap_err();
+    assert_eq!(error, "jhyau: min (6) max (5) bhanda thulo huna mildaina");
+    
+    let error = run("jhyau(0, 20000000000000000000)").unwrap_err();
+    assert_eq!(error, "jhyau: range 0 dekhi 20000000000000000000 samma dherai thulo cha");
+    assert!(run("jhyau(-1e19, 1e19)").is_err());
+    assert!(run("jhyau(0, 9007199254740992)").is_err());
+    assert!(run("jhyau(0, 9007199254740991)").is_ok());
+}
+
+#[test]
+fn test_samaya_is_positive() {
+    let output = run("bhan samaya() > 0").unwrap();
+    assert_eq!(output, "sahi\n");
+}
+
+#[test]
+fn test_ananta_ho_and_sankhya_ho() {
+    let output = run(r#"
+bhan ananta_ho(1e300)
+bhan sankhya_ho(5)
+bhan sankhya_ho("x")
+"#).unwrap();
+    assert_eq!(output, "galat\nsahi\ngalat\n");
+}
+
+#[test]
+fn test_napa_is_non_negative() {
+    let output = run(r#"
+kaam kaam_gara() {
+    maanau t = 0
+    dohoryau 100 { t = t + 1 }
+}
+bhan napa(kaam_gara) >= 0
+"#).unwrap();
+    assert_eq!(output, "sahi\n");
+}
+
+#[test]
+fn test_pakka_passes() {
+    let output = run("bhan pakka(1 == 1)").unwrap();
+    assert_eq!(output, "null\n");
+}
+
+#[test]
+fn test_pakka_fails() {
+    let error = run(r#"pakka(1 == 2, "ganit bigriyo")"#).unwrap_err();
+    assert_eq!(error, "ganit bigriyo");
+    
+    let error = run("pakka(galat)").unwrap_err();
+    assert_eq!(error, "Assertion fail bhayo");
+}
+
+#[test]
+fn test_pahilo_and_antim() {
+    let output = run("bhan pahilo([1, 2, 3])\nbhan antim([1, 2, 3])").unwrap();
+    assert_eq!(output, "1\n3\n");
+    
+    assert_eq!(run("pahilo([])").unwrap_err(), "pahilo: List khali cha");
+    assert_eq!(run("antim([])").unwrap_err(), "antim: List khali cha");
+}
+
+#[test]
+fn test_tukro_lists() {
+    let output = run(r#"
+bhan tukro([1, 2, 3, 4], 1, 3)
+bhan tukro([1, 2, 3, 4], -2, 100)
+bhan tukro([1, 2, 3, 4], 3, 1)
+"#).unwrap();
+    assert_eq!(output, "[2, 3]\n[3, 4]\n[]\n");
+}
+
+#[test]
+fn test_tukro_strings() {
+    let output = run(r#"
+bhan tukro("namaste", 0, 3)
+bhan tukro("नमस्ते", 0, 2)
+"#).unwrap();
+    assert_eq!(output, "nam\nनम\n");
+}
+
+#[test]
+fn test_kram_kunji_numeric_field_is_stable() {
+    let output = run(r#"
+maanau log = [{"naam": "b", "umar": 2}, {"naam": "a", "umar": 1}, {"naam": "c", "umar": 2}]
+pratyek p ma kram_kunji(log, "umar") {
+    likha p["naam"]
+}
+"#).unwrap();
+    assert_eq!(output, "abc");
+}
+
+#[test]
+fn test_kram_kunji_string_field() {
+    let output = run(r#"
+maanau log = [{"naam": "ga"}, {"naam": "ka"}, {"naam": "kha"}]
+pratyek p ma kram_kunji(log, "naam") {
+    bhan p["naam"]
+}
+"#).unwrap();
+    assert_eq!(output, "ga\nka\nkha\n");
+}
+
+#[test]
+fn test_kram_kunji_errors() {
+    let error = run(r#"kram_kunji([{"umar": 1}, {}], "umar")"#).unwrap_err();
+    assert_eq!(error, "kram_kunji: ek dictionary ma key \"umar\" chhaina");
+    
+    let error = run(r#"kram_kunji([1], "umar")"#).unwrap_err();
+    assert_eq!(error, "kram_kunji lai Dictionary haru ko List chahincha, Number payo");
+}
+
+#[test]
+fn test_ginti_lists() {
+    let output = run("bhan ginti([1, 2, 1, 3], 1)\nbhan ginti([1, 2], 5)").unwrap();
+    assert_eq!(output, "2\n0\n");
+}
+
+#[test]
+fn test_ginti_strings() {
+    // Occurrences don't overlap, so "aaaa" holds "aa" twice
+    let output = run(r#"
+bhan ginti("namaste", "a")
+bhan ginti("aaaa", "aa")
+"#).unwrap();
+    assert_eq!(output, "2\n2\n");
+    
+    let error = run("ginti(5, 1)").unwrap_err();
+    assert_eq!(error, "ginti lai List wa String chahincha, Number payo");
+}
+
+#[test]
+fn test_dashamlav() {
+    // 2.675 is stored as 2.67499..., so it rounds down
+    let output = run(r#"
+bhan dashamlav(3.14159, 2)
+bhan dashamlav(2.675, 2)
+bhan dashamlav(2.5, 0)
+"#).unwrap();
+    assert_eq!(output, "3.14\n2.67\n2\n");
+}
+
+#[test]
+fn test_dashamlav_bad_precision() {
+    let error = run("dashamlav(1, -1)").unwrap_err();
+    assert_eq!(error, "dashamlav ko precision negative nabhayeko purna sankhya hunu parcha, -1 payo");
+    
+    let error = run("dashamlav(1, 1.5)").unwrap_err();
+    assert_eq!(error, "dashamlav ko precision negative nabhayeko purna sankhya hunu parcha, 1.5 payo");
+}
+
+#[test]
+fn test_nepali_ank() {
+    let output = run(r#"
+bhan nepali_ank(123)
+bhan nepali_ank(-45)
+bhan nepali_ank(3.14)
+"#).unwrap();
+    assert_eq!(output, "१२३\n-४५\n३.१४\n");
+}
+
+#[test]
+fn test_jod_naksha_disjoint_keys() {
+    let output = run(r#"
+bhan jod_naksha({"p": 1}, {"q": 2}) == {"p": 1, "q": 2}
+bhan {"p": 1} + {"q": 2} == {"p": 1, "q": 2}
+"#).unwrap();
+    assert_eq!(output, "sahi\nsahi\n");
+}
+
+#[test]
+fn test_jod_naksha_second_wins() {
+    let output = run(r#"
+maanau a = {"x": 1, "y": 2}
+maanau b = {"y": 3, "z": 4}
+bhan jod_naksha(a, b) == {"x": 1, "y": 3, "z": 4}
+bhan a == {"x": 1, "y": 2}
+"#).unwrap();
+    assert_eq!(output, "sahi\nsahi\n");
+}
+
+#[test]
+fn test_jod_naksha_empty() {
+    let output = run(r#"
+bhan jod_naksha({}, {"a": 1}) == {"a": 1}
+bhan jod_naksha({"a": 1}, {}) == {"a": 1}
+bhan jod_naksha({}, {})
+"#).unwrap();
+    assert_eq!(output, "sahi\nsahi\n{}\n");
+    
+    let error = run("jod_naksha(1, {})").unwrap_err();
+    assert_eq!(error, "jod_naksha lai Dictionary chahincha, Number payo");
+}
+
+#[test]
+fn test_sundar_nested() {
+    let output = run(r#"bhan sundar({"naam": "Ram", "ank": [1, 2], "khali": [], "bhitra": {"a": sahi}})"#).unwrap();
+    assert_eq!(output, r#"{
+  "ank": [
+    1,
+    2
+  ],
+  "bhitra": {
+    "a": sahi
+  },
+  "khali": [],
+  "naam": "Ram"
+}
+"#);
+}
+
+#[test]
+fn test_sundar_lists_and_scalars() {
+    let output = run("bhan sundar([1, [2, 3]])\nbhan sundar(5)").unwrap();
+    assert_eq!(output, "[\n  1,\n  [\n    2,\n    3\n  ]\n]\n5\n");
+}
+
+#[test]
+fn test_json_round_trip() {
+    let output = run(r#"
+maanau d = {"naam": "Ram", "ank": [1, 2.5, -3], "bhitra": {"a": sahi, "b": json_padha("null")}, "s": "a\"b\n"}
+bhan json_padha(json_banau(d)) == d
+"#).unwrap();
+    assert_eq!(output, "sahi\n");
+}
+
+#[test]
+fn test_json_banau() {
+    let output = run(r#"bhan json_banau([1, "x", json_padha("null"), galat, {"k": 2.5}])"#).unwrap();
+    assert_eq!(output, "[1,\"x\",null,false,{\"k\":2.5}]\n");
+}
+
+#[test]
+fn test_json_padha_errors() {
+    let error = run(r#"json_padha("{\"a\": }")"#).unwrap_err();
+    assert_eq!(error, "JSON galat cha: anapekshit '}' at position 6");
+    
+    let error = run(r#"json_padha("[1, 2")"#).unwrap_err();
+    assert_eq!(error, "JSON galat cha: ',' wa ']' chahincha at position 5");
+}
+
+#[test]
+fn test_shreni_iterates_large_range_lazily() {
+    let output = run(r#"
+maanau total = 0
+pratyek i ma shreni(0, 1000000000000000) {
+    yedi i == 5 bhane { rok }
+    total = total + i
+}
+bhan total
+"#).unwrap();
+    assert_eq!(output, "10\n");
+}
+
+#[test]
+fn test_suchi_turns_range_into_list() {
+    let output = run(r#"
+bhan suchi(shreni(0, 5))
+bhan suchi(shreni(10, 0, -4))
+bhan suchi(shreni(3, 3))
+bhan suchi("ab")
+"#).unwrap();
+    assert_eq!(output, "[0, 1, 2, 3, 4]\n[10, 6, 2]\n[]\n[a, b]\n");
+    
+    assert_eq!(run("bhan suchi(5)").unwrap_err(), "suchi lai Range, List wa String chahincha, Number payo");
+}
+
+#[test]
+fn test_chha_key() {
+    let output = run(r#"
+maanau d = {"naam": "Ram", 1: "ek"}
+bhan chha_key(d, "naam"), chha_key(d, "umar")
+bhan has(d, 1), has(d, "1")
+"#).unwrap();
+    assert_eq!(output, "sahi galat\nsahi galat\n");
+    
+    assert_eq!(run("bhan chha_key([1], 1)").unwrap_err(), "chha_key lai Dictionary chahincha, List payo");
+}
+
+#[test]
+fn test_hatau_returns_copy_without_key() {
+    let output = run(r#"
+maanau d = {"a": 1, "b": 2}
+bhan hatau(d, "a") == {"b": 2}
+bhan hatau(d, "z") == d
+bhan d == {"a": 1, "b": 2}
+"#).unwrap();
+    assert_eq!(output, "sahi\nsahi\nsahi\n");
+}
+
+#[test]
+fn test_khoj_in_list() {
+    let output = run(r#"bhan khoj([1, "a", [2]], [2]), khoj([1, 2, 1], 1), khoj([1], 5), khoj([], 1)"#).unwrap();
+    assert_eq!(output, "2 0 -1 -1\n");
+}
+
+#[test]
+fn test_khoj_in_string_uses_char_index() {
+    let output = run(r#"
+bhan khoj("नमस्ते world", "world")
+bhan khoj("काठमाडौं", "माडौं")
+bhan khoj("hello", "z")
+"#).unwrap();
+    assert_eq!(output, "7\n3\n-1\n");
+    
+    assert_eq!(run("bhan khoj(5, 1)").unwrap_err(), "khoj lai List wa String chahincha, Number payo");
+    assert_eq!(run("bhan khoj(\"abc\", 1)").unwrap_err(), "khoj lai String chahincha, Number payo");
+}
+
+#[test]
+fn test_rup_fills_placeholders_in_order() {
+    let output = run(r#"
+bhan rup("Namaste {}, umer {}", "Ram", 25)
+bhan rup("{}{}", [1, 2], sahi)
+bhan rup("kehi chaina")
+"#).unwrap();
+    assert_eq!(output, "Namaste Ram, umer 25\n[1, 2]sahi\nkehi chaina\n");
+}
+
+#[test]
+fn test_rup_escaped_braces() {
+    let output = run(r#"bhan rup("{{}} {} {{{}}}", 1, 2)"#).unwrap();
+    assert_eq!(output, "{} 1 {2}\n");
+    
+    assert_eq!(run(r#"bhan rup("{x}")"#).unwrap_err(), "rup ma '{' pachi '}' wa '{' chahincha");
+}
+
+#[test]
+fn test_rup_argument_count_mismatch() {
+    assert_eq!(run(r#"bhan rup("{} {}", 1)"#).unwrap_err(), "rup ma 2 ota {} chan, tara 1 ota value diyo");
+    assert_eq!(run(r#"bhan rup("{}", 1, 2)"#).unwrap_err(), "rup ma 1 ota {} chan, tara 2 ota value diyo");
+    assert_eq!(run("bhan rup()").unwrap_err(), "Function rup expects at least 1 argument, got 0");
+}
+
+#[test]
+fn test_satya_for_each_type() {
+    let output = run(r#"
+kaam kehi_chaina() { }
+maanau f = kehi_chaina
+bhan satya(sahi), satya(galat)
+bhan satya(kehi_chaina())
+bhan satya(0), satya(-2.5)
+bhan satya(""), satya("hi")
+bhan satya([]), satya([0])
+bhan satya({}), satya({"a": 0})
+bhan satya(shreni(0, 0)), satya(shreni(0, 1))
+bhan satya(f)
+"#).unwrap();
+    assert_eq!(output, "sahi galat\ngalat\ngalat sahi\ngalat sahi\ngalat sahi\ngalat sahi\ngalat sahi\nsahi\n");
+}
+
+#[test]
+fn test_asatya_negates_satya() {
+    let output = run(r#"bhan asatya([]), asatya("hi"), asatya(0), asatya(sahi)"#).unwrap();
+    assert_eq!(output, "sahi galat sahi galat\n");
+    
+    assert_eq!(run("bhan satya(1, 2)").unwrap_err(), "Function satya expects 1 arguments, got 2");
+}
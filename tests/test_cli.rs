@@ -0,0 +1,170 @@
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+// Writes `source` to a file of its own under the target dir and runs the
+// khukuri binary with `args` followed by that file's path
+fn run_cli(name: &str, args: &[&str], source: &str) -> Output {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    let path = dir.join(format!("{}.nep", name));
+    std::fs::write(&path, source).unwrap();
+    Command::new(env!("CARGO_BIN_EXE_khukuri"))
+        .args(args)
+        .arg(&path)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+#[test]
+fn test_tokens_flag() {
+    let output = run_cli("tokens", &["--tokens"], "maanau x = 5\n");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), concat!(
+        "1:1 Keyword \"maanau\"\n",
+        "1:8 Identifier \"x\"\n",
+        "1:10 Operator \"=\"\n",
+        "1:12 Number \"5\"\n",
+        "1:13 Newline \"\\n\"\n",
+        "2:1 Eof \"\"\n",
+    ));
+}
+
+#[test]
+fn test_ast_flag_skips_interpretation() {
+    let output = run_cli("ast", &["--ast"], "bhan 5\n");
+    assert!(output.status.success());
+    let ast = stdout(&output);
+    assert!(ast.starts_with("Program("), "{}", ast);
+    assert!(ast.contains("Print("), "{}", ast);
+    assert!(!ast.lines().any(|line| line == "5"), "{}", ast);
+}
+
+#[test]
+fn test_check_reports_all_syntax_errors() {
+    let output = run_cli("check_errors", &["--check"], "maanau x = \nbhan (1 + \nmaanau y = 2\n");
+    assert!(!output.status.success());
+    let report = stdout(&output) + &stderr(&output);
+    assert!(report.contains("line 1 ma, column 12"), "{}", report);
+    assert!(report.contains("line 2 ma, column 11"), "{}", report);
+    assert!(report.contains("2 ota error bhetiyo"), "{}", report);
+}
+
+#[test]
+fn test_check_does_not_run_program() {
+    let output = run_cli("check_ok", &["--check"], "bhan \"chalyo\"\n");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "Syntax thik cha\n");
+}
+
+// A fixture under tests/fixtures
+fn fixture(path: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(path)
+}
+
+#[test]
+fn test_import_resolves_relative_to_importing_file() {
+    let output = Command::new(env!("CARGO_BIN_EXE_khukuri"))
+        .arg(fixture("imports/main.nep"))
+        .current_dir(env!("CARGO_TARGET_TMPDIR"))
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "Namaste\n42\n");
+}
+
+#[test]
+fn test_nested_subdirectory_imports() {
+    let output = Command::new(env!("CARGO_BIN_EXE_khukuri"))
+        .arg(fixture("nested/main.nep"))
+        .current_dir(env!("CARGO_TARGET_TMPDIR"))
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "12\n");
+}
+
+#[test]
+fn test_time_flag_keeps_program_output() {
+    let output = run_cli("timed", &["--time"], "bhan 1 + 2\n");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "3\n");
+    assert!(stderr(&output).starts_with("Samaya lagyo: "), "{}", stderr(&output));
+}
+
+#[test]
+fn test_comment_only_file() {
+    let output = run_cli("comment_only", &[], "// kei chhaina\n\n");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "");
+}
+
+#[test]
+fn test_fmt_is_idempotent() {
+    let canonical = concat!(
+        "maanau x = 1_000\n",
+        "maanau y = .5\n",
+        "\n",
+        "kaam jod(a, b) {\n",
+        "    pathau a + b\n",
+        "}\n",
+        "\n",
+        "yedi x > 5 bhane {\n",
+        "    bhan jod(x, y)\n",
+        "} natra {\n",
+        "    bhan \"sano\\tcha\"\n",
+        "}\n",
+        "pratyek n ma [1, 2.50, 3e2] {\n",
+        "    bhan n\n",
+        "}\n",
+    );
+    let output = run_cli("fmt_canonical", &["fmt"], canonical);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), canonical);
+}
+
+#[test]
+fn test_fmt_keeps_number_literals() {
+    let output = run_cli("fmt_numbers", &["fmt"], "bhan   1_000+.5\n");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "bhan 1_000 + .5\n");
+}
+
+#[test]
+fn test_fmt_refuses_comments() {
+    let output = run_cli("fmt_comments", &["fmt"], "// jod\nbhan 1 + 2\n");
+    assert!(!output.status.success());
+    assert!(stdout(&output).is_empty(), "{}", stdout(&output));
+    assert!(stderr(&output).contains("comment bhayeko file format garna sakdaina"), "{}", stderr(&output));
+    
+    // "//" inside a string is not a comment
+    let output = run_cli("fmt_slashes", &["fmt"], "bhan \"http://x\"\n");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "bhan \"http://x\"\n");
+}
+
+#[test]
+fn test_quiet_flag() {
+    let output = run_cli("quiet", &["--quiet"], "bhan 1 + 2\n");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "");
+    
+    let output = run_cli("quiet_error", &["--quiet"], "bhan 1\nbhan nabhayeko\n");
+    assert!(!output.status.success());
+    assert_eq!(stdout(&output), "");
+    assert!(stderr(&output).contains("Undefined variable: nabhayeko"), "{}", stderr(&output));
+}
+
+#[test]
+fn test_fmt_keeps_assignments_and_steps_apart() {
+    let canonical = "maanau x = 5\nx = x - 1\nx = x - -1\nbadha x\nghata x\n";
+    let output = run_cli("fmt_steps", &["fmt"], canonical);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), canonical);
+}
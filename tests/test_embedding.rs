@@ -0,0 +1,278 @@
+use khukuri::interpreter::Interpreter;
+use khukuri::prelude::{NativeFunction, PreludeModule};
+use khukuri::value::Value;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+// An interpreter whose printed lines are collected into the returned Vec
+fn collecting_interpreter() -> (Interpreter, Rc<RefCell<Vec<String>>>) {
+    let lines = Rc::new(RefCell::new(Vec::new()));
+    let collected = lines.clone();
+    let mut interpreter = Interpreter::new();
+    interpreter.on_print(Box::new(move |text: &str| collected.borrow_mut().push(text.to_string())));
+    (interpreter, lines)
+}
+
+#[test]
+fn test_on_print_collects_lines() {
+    let (mut interpreter, lines) = collecting_interpreter();
+    interpreter.eval_source(r#"
+bhan "namaste"
+bhan 1, 2
+likha "a"
+bhan 3 + 4
+"#).unwrap();
+    
+    assert_eq!(*lines.borrow(), vec!["namaste", "1 2", "a", "7"]);
+}
+
+#[test]
+fn test_snapshot_restore() {
+    let (mut interpreter, lines) = collecting_interpreter();
+    interpreter.eval_source("maanau x = [1, 2]").unwrap();
+    let snapshot = interpreter.snapshot();
+    
+    interpreter.eval_source("x[0] = 9\nmaanau y = 5\nkaam f() { pathau 1 }").unwrap();
+    interpreter.restore(snapshot);
+    
+    interpreter.eval_source("bhan x").unwrap();
+    assert_eq!(*lines.borrow(), vec!["[1, 2]"]);
+    assert_eq!(interpreter.eval_source("bhan y").unwrap_err(), "Undefined variable: y");
+    assert!(interpreter.eval_source("f()").is_err());
+}
+
+#[test]
+fn test_eval_source_keeps_state() {
+    let (mut interpreter, lines) = collecting_interpreter();
+    interpreter.eval_source("maanau x = 20").unwrap();
+    let value = interpreter.eval_source("bhan x\nx + 1").unwrap();
+    
+    assert_eq!(value.to_string(), "21");
+    assert_eq!(*lines.borrow(), vec!["20"]);
+}
+
+#[test]
+fn test_eval_source_recovers_from_failed_call() {
+    let (mut interpreter, lines) = collecting_interpreter();
+    interpreter.eval_source("kaam f() {\n    maanau a = 1\n    pathau nabhayeko\n}").unwrap();
+    assert!(interpreter.eval_source("f()").is_err());
+    
+    // y must land in the global scope, not the one f() left open
+    interpreter.eval_source("maanau y = 1").unwrap();
+    assert!(interpreter.globals().iter().any(|(name, _)| name == "y"));
+    assert!(interpreter.eval_source("bhan a").is_err());
+    interpreter.eval_source("bhan y").unwrap();
+    assert_eq!(*lines.borrow(), vec!["1"]);
+}
+
+// Appends to a Vec<u8> the test still holds after the interpreter takes the writer
+struct VecWriter(Rc<RefCell<Vec<u8>>>);
+
+impl Write for VecWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+    
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_with_writer_collects_bytes() {
+    let bytes = Rc::new(RefCell::new(Vec::new()));
+    let mut interpreter = Interpreter::with_writer(Box::new(VecWriter(bytes.clone())));
+    interpreter.eval_source("bhan \"namaste\", 1\nlikha \"नमस्ते\"\nbhan [1, 2]").unwrap();
+    
+    assert_eq!(*bytes.borrow(), "namaste 1\nनमस्ते[1, 2]\n".as_bytes());
+}
+
+#[test]
+fn test_quiet_writes_nothing_but_still_evaluates() {
+    let bytes = Rc::new(RefCell::new(Vec::new()));
+    let mut interpreter = Interpreter::with_writer(Box::new(VecWriter(bytes.clone())));
+    interpreter.set_quiet(true);
+    
+    let value = interpreter.eval_source(r#"
+maanau ginti = 0
+kaam badhau() {
+    ginti = ginti + 1
+    pathau ginti
+}
+bhan badhau()
+likha badhau()
+ginti
+"#).unwrap();
+    assert_eq!(value.to_string(), "2");
+    
+    let error = interpreter.eval_source("bhan 1, nabhayeko").unwrap_err();
+    assert_eq!(error, "Undefined variable: nabhayeko");
+    assert!(bytes.borrow().is_empty());
+}
+
+fn strict_interpreter() -> Interpreter {
+    let mut interpreter = Interpreter::new();
+    interpreter.set_strict(true);
+    interpreter
+}
+
+#[test]
+fn test_strict_typed_declaration() {
+    let value = strict_interpreter().eval_source("maanau x: Number = 5\nx = 6\nx").unwrap();
+    assert_eq!(value.to_string(), "6");
+}
+
+#[test]
+fn test_strict_mismatched_initializer() {
+    let error = strict_interpreter().eval_source("maanau x: Number = \"a\"").unwrap_err();
+    assert_eq!(error, "Type milena: 'x' Number hunu parcha, String payo");
+}
+
+#[test]
+fn test_strict_mismatched_assignment() {
+    let error = strict_interpreter().eval_source("maanau x: Number = 5\nx = \"a\"").unwrap_err();
+    assert_eq!(error, "Type milena: 'x' Number hunu parcha, String payo");
+    
+    // Hints are only checked in strict mode
+    assert!(Interpreter::new().eval_source("maanau x: Number = 5\nx = \"a\"").is_ok());
+}
+
+#[test]
+fn test_step_limit_stops_infinite_loop() {
+    let mut interpreter = Interpreter::with_step_limit(10_000);
+    let error = interpreter.eval_source("jaba samma sahi {\n    maanau x = 1\n}").unwrap_err();
+    assert_eq!(error, "Program dherai lamo bhayo (step limit)");
+}
+
+#[test]
+fn test_step_limit_stops_empty_loop() {
+    let error = Interpreter::with_step_limit(10_000).eval_source("jaba samma sahi { }").unwrap_err();
+    assert_eq!(error, "Program dherai lamo bhayo (step limit)");
+}
+
+#[test]
+fn test_step_limit_allows_short_programs() {
+    let value = Interpreter::with_step_limit(10_000).eval_source("maanau t = 0\ndohoryau 10 { t = t + 1 }\nt").unwrap();
+    assert_eq!(value.to_string(), "10");
+}
+
+#[test]
+fn test_top_level_pathau_value() {
+    let value = Interpreter::new().eval_source("maanau x = 2\npathau x * 3\nx = 100").unwrap();
+    assert_eq!(value.to_string(), "6");
+}
+
+#[test]
+fn test_failed_closure_call_leaves_no_scope() {
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_source(r#"
+kaam g() {
+    maanau x = 1
+    kaam h() { pathau x + nabhayeko }
+    pathau h
+}
+maanau hh = g()
+maanau sandesh = ""
+kosis { hh() } samat e { sandesh = e }
+maanau y = 2
+"#).unwrap();
+    
+    let names: Vec<String> = interpreter.globals().into_iter().map(|(name, _)| name).collect();
+    assert_eq!(names, vec!["hh", "sandesh", "y"]);
+}
+
+#[test]
+fn test_lenient_index_mode() {
+    let source = "maanau l = [1, 2]\nmaanau s = \"ab\"\nbhan l[5], s[2]";
+    
+    let (mut interpreter, _) = collecting_interpreter();
+    assert_eq!(interpreter.eval_source(source).unwrap_err(), "List index 5 out of bounds");
+    
+    let (mut interpreter, lines) = collecting_interpreter();
+    interpreter.set_lenient_index(true);
+    interpreter.eval_source(source).unwrap();
+    assert_eq!(*lines.borrow(), vec!["null null"]);
+}
+
+#[test]
+fn test_lenient_index_still_rejects_writes_and_missing_keys() {
+    let (mut interpreter, _) = collecting_interpreter();
+    interpreter.set_lenient_index(true);
+    assert_eq!(interpreter.eval_source("maanau l = [1]\nl[3] = 1").unwrap_err(), "List index 3 out of bounds");
+    assert_eq!(interpreter.eval_source("maanau d = {\"a\": 1}\nd[\"b\"]").unwrap_err(), "Key 'b' not found in dictionary");
+}
+
+#[test]
+fn test_register_module() {
+    let (mut interpreter, lines) = collecting_interpreter();
+    let mut functions: HashMap<String, NativeFunction> = HashMap::new();
+    functions.insert("tin_guna".to_string(), Rc::new(|args: &[Value]| match args {
+        [Value::Number(n)] => Ok(Value::Number(n * 3.0)),
+        _ => Err("tin_guna lai euta Number chahincha".to_string()),
+    }));
+    interpreter.register_module("sahayak", PreludeModule::Native(functions));
+    interpreter.register_module("abhibadan", PreludeModule::Source("kaam namaste(naam) { pathau \"Namaste \" + naam }".to_string()));
+    
+    assert_eq!(interpreter.eval_source("bhan tin_guna(2)").unwrap_err(), "Undefined function: tin_guna");
+    interpreter.eval_source("aayaat sahayak\naayaat abhibadan\nbhan tin_guna(2)\nbhan namaste(\"Ram\")").unwrap();
+    assert_eq!(*lines.borrow(), vec!["6", "Namaste Ram"]);
+}
+
+#[test]
+fn test_nan_is_not_equal_to_itself() {
+    let (mut interpreter, lines) = collecting_interpreter();
+    interpreter.register_native("nan", |_: &[Value]| Ok(Value::Number(f64::NAN)));
+    interpreter.eval_source("maanau n = nan()\nbhan n == n, n != n, sankhya_ho(n)").unwrap();
+    assert_eq!(*lines.borrow(), vec!["galat sahi galat"]);
+}
+
+#[test]
+fn test_register_native() {
+    let (mut interpreter, lines) = collecting_interpreter();
+    interpreter.register_native("double", |args: &[Value]| match args {
+        [Value::Number(n)] => Ok(Value::Number(n * 2.0)),
+        _ => Err("double lai euta Number chahincha".to_string()),
+    });
+    
+    interpreter.eval_source("bhan double(21)\nbhan double(double(1) + 1)").unwrap();
+    assert_eq!(*lines.borrow(), vec!["42", "6"]);
+    assert_eq!(interpreter.eval_source("double(\"a\")").unwrap_err(), "double lai euta Number chahincha");
+    
+    // The host's function wins over a kaam of the same name
+    interpreter.eval_source("kaam double(x) { pathau 0 }\nbhan double(5)").unwrap();
+    assert_eq!(lines.borrow().last().unwrap(), "10");
+}
+
+#[test]
+fn test_rok_outside_loop_fails_before_running() {
+    let (mut interpreter, lines) = collecting_interpreter();
+    let error = interpreter.eval_source("bhan \"pahila\"\nrok").unwrap_err();
+    assert!(error.contains("'rok' loop bhitra matra prayog garna milcha at line 2"), "{}", error);
+    assert!(lines.borrow().is_empty());
+}
+
+#[test]
+fn test_sthiti_empty_after_failed_eval() {
+    let (mut interpreter, lines) = collecting_interpreter();
+    interpreter.eval_source("kaam f() { pathau nabhayeko }").unwrap();
+    assert!(interpreter.eval_source("f()").is_err());
+    interpreter.eval_source("bhan sthiti()").unwrap();
+    assert_eq!(*lines.borrow(), vec!["[]"]);
+}
+
+#[test]
+fn test_float_precision() {
+    let source = "maanau x = 0.1 + 0.2\nbhan x\nlikha [x, 2.345, 7]\nbhan \"\"\nbhan x == 0.3";
+    
+    let (mut interpreter, lines) = collecting_interpreter();
+    interpreter.eval_source(source).unwrap();
+    assert_eq!(*lines.borrow(), vec!["0.30000000000000004", "[0.30000000000000004, 2.345, 7]", "", "galat"]);
+    
+    // Only the printed text is rounded, x itself is unchanged
+    let (mut interpreter, lines) = collecting_interpreter();
+    interpreter.set_float_precision(Some(2));
+    interpreter.eval_source(source).unwrap();
+    assert_eq!(*lines.borrow(), vec!["0.3", "[0.3, 2.35, 7]", "", "galat"]);
+}
@@ -0,0 +1,16 @@
+use khukuri::environment::Environment;
+use khukuri::value::Value;
+
+#[test]
+fn test_globals_skip_local_scopes() {
+    let mut environment = Environment::new();
+    environment.define("x".to_string(), Value::Number(1.0)).unwrap();
+    environment.push_scope();
+    environment.define("a".to_string(), Value::Number(2.0)).unwrap();
+    
+    let mut globals: Vec<(String, Value)> = environment.globals()
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect();
+    globals.sort_by(|(a, _), (b, _)| a.cmp(b));
+    assert_eq!(globals, vec![("x".to_string(), Value::Number(1.0))]);
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use common::run;
+
+// Relative imports in these programs resolve from the package root, where
+// cargo runs the tests
+
+#[test]
+fn test_aayaat_le_returns_module_value() {
+    let output = run(r#"
+maanau m = aayaat_le "tests/fixtures/imports/sahayak.nep"
+bhan m["dobar"](4)
+bhan m.dobar(5)
+bhan m.ABHIBADAN
+"#).unwrap();
+    assert_eq!(output, "8\n10\nNamaste\n");
+}
+
+#[test]
+fn test_aayaat_le_keeps_names_out_of_scope() {
+    let error = run(r#"
+maanau m = aayaat_le "tests/fixtures/imports/sahayak.nep"
+dobar(1)
+"#).unwrap_err();
+    assert_eq!(error, "Undefined function: dobar");
+}
+
+#[test]
+fn test_aliased_import() {
+    let output = run(r#"
+aayaat "tests/fixtures/imports/sahayak.nep" naam ma sahayak
+bhan sahayak.dobar(21)
+bhan sahayak.ABHIBADAN
+"#).unwrap();
+    assert_eq!(output, "42\nNamaste\n");
+}
+
+#[test]
+fn test_aliased_import_avoids_collision() {
+    let output = run(r#"
+kaam dobar(x) {
+    pathau x + x + 1
+}
+aayaat "tests/fixtures/imports/sahayak.nep" naam ma sahayak
+bhan dobar(1)
+bhan sahayak.dobar(1)
+"#).unwrap();
+    assert_eq!(output, "3\n2\n");
+}
+
+#[test]
+fn test_import_exposes_functions_and_variables() {
+    let output = run(r#"
+aayaat "tests/fixtures/imports/sahayak.nep"
+bhan dobar(21)
+bhan ABHIBADAN
+"#).unwrap();
+    assert_eq!(output, "42\nNamaste\n");
+}
+
+#[test]
+fn test_import_export_value() {
+    let output = run(r#"
+maanau m = aayaat "tests/fixtures/imports/ganit.nep"
+bhan m["jod"](2, 3)
+bhan m.guna(2, 3)
+"#).unwrap();
+    assert_eq!(output, "ganit load bhayo\n5\n6\n");
+}
+
+#[test]
+fn test_import_runs_module_once() {
+    let output = run(r#"
+maanau m = aayaat "tests/fixtures/imports/ganit.nep"
+maanau n = aayaat "tests/fixtures/imports/ganit.nep"
+bhan n.jod(1, 1)
+"#).unwrap();
+    assert_eq!(output, "ganit load bhayo\n2\n");
+}
+
+#[test]
+fn test_failed_import_is_not_cached() {
+    let output = run(r#"
+dohoryau 2 {
+    kosis {
+        aayaat "tests/fixtures/imports/nabhayeko.nep"
+        bhan "chalyo"
+    } samat e {
+        bhan "fail"
+    }
+}
+"#).unwrap();
+    assert_eq!(output, "fail\nfail\n");
+}
+
+#[test]
+fn test_named_prelude_module() {
+    let output = run(r#"
+aayaat ganit
+bhan ghaat(2, 10)
+bhan mahattam(12, 18), laghuttam(4, 6)
+bhan sima(15, 0, 10)
+"#).unwrap();
+    assert_eq!(output, "1024\n6 12\n10\n");
+}
+
+#[test]
+fn test_named_prelude_module_as_value() {
+    let output = run("maanau g = aayaat_le ganit\nbhan g.kramguni(5)").unwrap();
+    assert_eq!(output, "120\n");
+}
+
+#[test]
+fn test_import_inside_kaam_defines_global_functions() {
+    let output = run(r#"
+kaam lyau() {
+    aayaat "tests/fixtures/imports/sahayak.nep"
+}
+lyau()
+bhan dobar(4)
+"#).unwrap();
+    assert_eq!(output, "8\n");
+}
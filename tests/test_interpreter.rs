@@ -0,0 +1,834 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn test_sthir_declaration() {
+    let output = run("sthir pi = 3.14\nbhan pi * 2").unwrap();
+    assert_eq!(output, "6.28\n");
+}
+
+#[test]
+fn test_sthir_reassignment() {
+    let error = run("sthir x = 1\nx = 2").unwrap_err();
+    assert_eq!(error, "Sthir char badalna sakdaina: x");
+}
+
+#[test]
+fn test_sthir_redeclaration() {
+    let error = run("sthir x = 1\nmaanau x = 2\nbhan x").unwrap_err();
+    assert_eq!(error, "Sthir char feri declare garna sakdaina: x");
+}
+
+#[test]
+fn test_sthir_shadowed_in_inner_scope() {
+    let output = run(r#"
+sthir x = 1
+yedi sahi bhane {
+    maanau x = 3
+    bhan x
+}
+bhan x
+"#).unwrap();
+    assert_eq!(output, "3\n1\n");
+}
+
+#[test]
+fn test_undefined_variable_suggestion() {
+    let error = run("maanau naam = \"Ram\"\nbhan nam").unwrap_err();
+    assert_eq!(error, "Undefined variable: nam. Kya tapai le 'naam' bhanna khojeko?");
+}
+
+#[test]
+fn test_undefined_variable_without_suggestion() {
+    let error = run("maanau naam = \"Ram\"\nbhan zzzzz").unwrap_err();
+    assert_eq!(error, "Undefined variable: zzzzz");
+}
+
+#[test]
+fn test_do_while_runs_body_once() {
+    let output = run(r#"
+maanau i = 10
+gara {
+    bhan i
+} jaba samma i < 5
+"#).unwrap();
+    assert_eq!(output, "10\n");
+}
+
+#[test]
+fn test_do_while_break_and_continue() {
+    let output = run(r#"
+maanau j = 0
+gara {
+    j = j + 1
+    yedi j == 2 bhane { jane }
+    yedi j == 4 bhane { rok }
+    bhan j
+} jaba samma j < 10
+"#).unwrap();
+    assert_eq!(output, "1\n3\n");
+}
+
+#[test]
+fn test_default_parameters() {
+    let output = run(r#"
+kaam greet(naam, greeting = "Namaste") {
+    pathau greeting + " " + naam
+}
+bhan greet("Ram")
+bhan greet("Ram", "Hello")
+"#).unwrap();
+    assert_eq!(output, "Namaste Ram\nHello Ram\n");
+}
+
+#[test]
+fn test_default_parameter_uses_earlier_parameters() {
+    let output = run("kaam f(a, b = a * 2) { pathau b }\nbhan f(3)").unwrap();
+    assert_eq!(output, "6\n");
+}
+
+#[test]
+fn test_missing_required_parameter() {
+    let error = run("kaam g(a, b = 1) { pathau a }\ng()").unwrap_err();
+    assert_eq!(error, "Function g expects 1 to 2 arguments, got 0");
+}
+
+#[test]
+fn test_variadic_function() {
+    let output = run(r#"
+kaam jamma(...nums) {
+    maanau total = 0
+    pratyek n ma nums {
+        total = total + n
+    }
+    pathau total
+}
+bhan jamma()
+bhan jamma(5)
+bhan jamma(1, 2, 3)
+"#).unwrap();
+    assert_eq!(output, "0\n5\n6\n");
+}
+
+#[test]
+fn test_variadic_after_named_parameter() {
+    let output = run("kaam f(a, ...rest) { pathau rest }\nbhan f(1)\nbhan f(1, 2, 3)").unwrap();
+    assert_eq!(output, "[]\n[2, 3]\n");
+    
+    let error = run("kaam f(a, ...rest) { pathau rest }\nf()").unwrap_err();
+    assert_eq!(error, "Function f expects at least 1 arguments, got 0");
+}
+
+#[test]
+fn test_bitwise_operators() {
+    let output = run(r#"
+bhan 6 & 3
+bhan 6 | 3
+bhan 6 ^ 3
+bhan 1 << 4
+bhan 256 >> 2
+"#).unwrap();
+    assert_eq!(output, "2\n7\n5\n16\n64\n");
+}
+
+#[test]
+fn test_bitwise_needs_whole_numbers() {
+    let error = run("bhan 1.5 & 1").unwrap_err();
+    assert_eq!(error, "Bitwise '&' lai purna sankhya chahincha, 1.5 payo");
+}
+
+#[test]
+fn test_leading_dot_number() {
+    let output = run("bhan .5 + .5").unwrap();
+    assert_eq!(output, "1\n");
+}
+
+#[test]
+fn test_digit_separators_value() {
+    let output = run("bhan 1_000 + 2.5_0\nbhan 1e1_0").unwrap();
+    assert_eq!(output, "1002.5\n10000000000\n");
+}
+
+#[test]
+fn test_range_is_not_materialized() {
+    // A list of 10^15 numbers could never be allocated
+    let output = run(r#"
+maanau total = 0
+pratyek i ma shrinkhala(0, 1000000000000000) {
+    yedi i == 3 bhane { rok }
+    total = total + i
+}
+bhan total
+"#).unwrap();
+    assert_eq!(output, "3\n");
+}
+
+#[test]
+fn test_range_length_and_index() {
+    let output = run(r#"
+maanau r = shrinkhala(0, 10, 3)
+bhan lambai(r)
+bhan lambai(shrinkhala(0, 1000000000000000))
+bhan r[2]
+"#).unwrap();
+    assert_eq!(output, "4\n1000000000000000\n6\n");
+}
+
+#[test]
+fn test_list_equality() {
+    let output = run(r#"
+bhan [1, 2] == [1, 2]
+bhan [1, 2] == [2, 1]
+bhan [1, 2] != [2, 1]
+bhan [[1, [2]], 3] == [[1, [2]], 3]
+"#).unwrap();
+    assert_eq!(output, "sahi\ngalat\nsahi\nsahi\n");
+}
+
+#[test]
+fn test_dictionary_equality() {
+    let output = run(r#"
+bhan {"a": 1, "b": 2} == {"b": 2, "a": 1}
+bhan {"a": 1} == {"a": 2}
+bhan {"a": [1]} != {"a": [1]}
+"#).unwrap();
+    assert_eq!(output, "sahi\ngalat\ngalat\n");
+}
+
+#[test]
+fn test_copied_nested_list_is_independent() {
+    let output = run(r#"
+maanau a = [[1, 2], {"k": [3]}]
+maanau b = a
+b[0][0] = 99
+b[1]["k"][0] = 77
+bhan a
+bhan b
+"#).unwrap();
+    assert_eq!(output, "[[1, 2], {\"k\": [3]}]\n[[99, 2], {\"k\": [77]}]\n");
+}
+
+#[test]
+fn test_copied_dictionary_of_lists_is_independent() {
+    let output = run(r#"
+maanau d = {"l": [1]}
+maanau e = d
+e["l"][0] = 5
+bhan d["l"]
+"#).unwrap();
+    assert_eq!(output, "[1]\n");
+}
+
+#[test]
+fn test_argument_is_a_copy() {
+    let output = run(r#"
+kaam badal(x) {
+    x[0] = 0
+    pathau x
+}
+maanau a = [1, 2]
+bhan badal(a)
+bhan a
+"#).unwrap();
+    assert_eq!(output, "[0, 2]\n[1, 2]\n");
+}
+
+#[test]
+fn test_nested_index_assignment_2d_list() {
+    let output = run(r#"
+maanau grid = [[0, 0], [0, 0]]
+grid[0][1] = 5
+grid[1][0] = grid[0][1] + 1
+bhan grid
+"#).unwrap();
+    assert_eq!(output, "[[0, 5], [6, 0]]\n");
+}
+
+#[test]
+fn test_nested_index_assignment_dictionary_of_lists() {
+    let output = run(r#"
+maanau d = {"a": [1, 2]}
+d["a"][1] = 9
+bhan d["a"]
+"#).unwrap();
+    assert_eq!(output, "[1, 9]\n");
+}
+
+#[test]
+fn test_likha_has_no_newline() {
+    let output = run(r#"
+likha "a"
+likha "b"
+bhan "c"
+"#).unwrap();
+    assert_eq!(output, "abc\n");
+}
+
+#[test]
+fn test_print_multiple_arguments() {
+    let output = run("bhan 1\nbhan 1, \"a\", [2]\nbhan\nbhan \"x\"").unwrap();
+    assert_eq!(output, "1\n1 a [2]\n\nx\n");
+}
+
+#[test]
+fn test_chhan_matching_arm_and_default() {
+    let output = run(r#"
+kaam naam(x) {
+    chhan x {
+        1 bhane { pathau "ek" }
+        2 bhane { pathau "dui" }
+        natra { pathau "dherai" }
+    }
+}
+bhan naam(2)
+bhan naam(9)
+"#).unwrap();
+    assert_eq!(output, "dui\ndherai\n");
+}
+
+#[test]
+fn test_chhan_without_match_or_default() {
+    let output = run(r#"
+chhan 5 {
+    1 bhane { bhan "ek" }
+}
+bhan "sakiyo"
+"#).unwrap();
+    assert_eq!(output, "sakiyo\n");
+}
+
+#[test]
+fn test_index_must_be_whole_number() {
+    let output = run("maanau l = [1, 2, 3]\nbhan l[2]").unwrap();
+    assert_eq!(output, "3\n");
+    
+    let error = run("maanau l = [1, 2, 3]\nbhan l[2.9]").unwrap_err();
+    assert_eq!(error, "Index purna sankhya hunu parcha, payo 2.9");
+    
+    let error = run("maanau l = [1, 2, 3]\nbhan l[-1]").unwrap_err();
+    assert_eq!(error, "Index negative huna mildaina, payo -1");
+}
+
+#[test]
+fn test_number_and_boolean_dictionary_keys() {
+    let output = run(r#"
+maanau d = {1: "ek", sahi: "ho", "1": "string"}
+bhan d[1]
+bhan d[1.0]
+bhan d[sahi]
+bhan d["1"]
+"#).unwrap();
+    assert_eq!(output, "ek\nek\nho\nstring\n");
+}
+
+#[test]
+fn test_list_is_not_a_dictionary_key() {
+    let error = run("maanau d = {[1]: 2}").unwrap_err();
+    assert_eq!(error, "List dictionary key huna sakdaina");
+}
+
+#[test]
+fn test_jane_and_rok_one_yedi_deep() {
+    let output = run(r#"
+pratyek i ma [1, 2, 3, 4, 5] {
+    yedi i == 2 bhane { jane }
+    yedi i == 4 bhane { rok }
+    bhan i
+}
+"#).unwrap();
+    assert_eq!(output, "1\n3\n");
+}
+
+#[test]
+fn test_jane_and_rok_two_yedi_deep() {
+    let output = run(r#"
+pratyek i ma [1, 2, 3, 4, 5] {
+    yedi i > 1 bhane {
+        yedi i == 2 bhane { jane }
+        yedi i == 4 bhane { rok }
+    }
+    bhan i
+}
+maanau n = 0
+jaba samma n < 6 {
+    n = n + 1
+    yedi n % 2 == 0 bhane {
+        yedi sahi bhane { jane }
+    }
+    yedi n == 5 bhane { rok }
+    bhan n
+}
+"#).unwrap();
+    assert_eq!(output, "1\n3\n1\n3\n");
+}
+
+#[test]
+fn test_arithmetic_overflow() {
+    let error = run("bhan 1e308 * 10").unwrap_err();
+    assert_eq!(error, "Sankhya ko simana naghyo (overflow)");
+    
+    let output = run("bhan 1e300 * 10 > 1e300").unwrap();
+    assert_eq!(output, "sahi\n");
+}
+
+#[test]
+fn test_birsau() {
+    let error = run("maanau x = [1, 2]\nbirsau x\nbhan x").unwrap_err();
+    assert_eq!(error, "Undefined variable: x");
+}
+
+#[test]
+fn test_birsau_undefined() {
+    let error = run("birsau y").unwrap_err();
+    assert_eq!(error, "Undefined variable: y");
+}
+
+#[test]
+fn test_birsau_uncovers_shadowed_variable() {
+    let output = run(r#"
+maanau x = 1
+yedi sahi bhane {
+    maanau x = 2
+    birsau x
+    bhan x
+}
+"#).unwrap();
+    assert_eq!(output, "1\n");
+}
+
+#[test]
+fn test_birsau_sthir() {
+    let error = run("sthir x = 1\nbirsau x").unwrap_err();
+    assert_eq!(error, "Sthir char birsauna sakdaina: x");
+}
+
+#[test]
+fn test_string_repetition() {
+    let output = run("bhan \"ab\" * 3\nbhan 2 * \"ab\"\nbhan \"x\" * 0").unwrap();
+    assert_eq!(output, "ababab\nabab\n\n");
+}
+
+#[test]
+fn test_string_repetition_bad_counts() {
+    let error = run("bhan \"ab\" * -1").unwrap_err();
+    assert_eq!(error, "String dohoryauna count purna sankhya (0 wa badi) hunu parcha, payo -1");
+    
+    let error = run("bhan \"ab\" * 1.5").unwrap_err();
+    assert_eq!(error, "String dohoryauna count purna sankhya (0 wa badi) hunu parcha, payo 1.5");
+}
+
+#[test]
+fn test_string_repetition_too_long() {
+    let error = run("bhan \"x\" * 100000000000000000000").unwrap_err();
+    assert_eq!(error, "String dohoryauna count dherai thulo cha, payo 100000000000000000000");
+    
+    let error = run("bhan \"xy\" * 268435456").unwrap_err();
+    assert_eq!(error, "String dohoryauda 268435456 bytes bhanda lamo huncha");
+}
+
+#[test]
+fn test_if_expression_value() {
+    let output = run(r#"
+maanau x = yedi 1 > 0 bhane {
+    maanau a = 2
+    a * 10
+} natra {
+    0
+}
+bhan x
+"#).unwrap();
+    assert_eq!(output, "20\n");
+}
+
+#[test]
+fn test_if_expression_without_value() {
+    let output = run(r#"
+maanau y = yedi galat bhane { 1 } natra { maanau b = 1 }
+bhan y
+maanau z = yedi galat bhane { 1 }
+bhan z
+"#).unwrap();
+    assert_eq!(output, "null\nnull\n");
+}
+
+#[test]
+fn test_empty_and_comment_only_programs() {
+    for source in ["", "   \n\t\n", "// hi", "// ek\n\n// dui\n"] {
+        assert_eq!(run(source).unwrap(), "", "{:?}", source);
+    }
+}
+
+#[test]
+fn test_safe_index_access() {
+    let output = run(r#"
+maanau d = {"a": 1}
+bhan d?["a"]
+bhan d?["b"]
+maanau n = 5
+bhan n?[0]
+"#).unwrap();
+    assert_eq!(output, "1\nnull\nnull\n");
+}
+
+#[test]
+fn test_safe_index_keeps_wrong_index_errors() {
+    let output = run("maanau l = [1]\nmaanau s = \"ab\"\nbhan l?[5], s?[2]").unwrap();
+    assert_eq!(output, "null null\n");
+    
+    assert_eq!(run("maanau l = [1]\nbhan l?[\"a\"]").unwrap_err(), "Cannot index List with String");
+    assert_eq!(run("maanau l = [1]\nbhan l?[-1]").unwrap_err(), "Index negative huna mildaina, payo -1");
+}
+
+#[test]
+fn test_destructuring() {
+    let output = run(r#"
+maanau [a, b] = [1, 2]
+maanau {x, y} = {"x": 3, "y": 4, "z": 5}
+bhan a, b, x, y
+"#).unwrap();
+    assert_eq!(output, "1 2 3 4\n");
+}
+
+#[test]
+fn test_destructuring_errors() {
+    let error = run("maanau [a, b] = [1]").unwrap_err();
+    assert_eq!(error, "Destructuring ma 2 ota naam chan, tara List ma 1 ota item");
+    
+    let error = run(r#"maanau {x} = {"y": 1}"#).unwrap_err();
+    assert_eq!(error, "Destructuring: dictionary ma key 'x' chhaina");
+}
+
+#[test]
+fn test_top_level_pathau_stops_program() {
+    let output = run("bhan \"pahile\"\npathau 5\nbhan \"pachhi\"").unwrap();
+    assert_eq!(output, "pahile\n");
+}
+
+#[test]
+fn test_kosis_catches_error() {
+    let output = run(r#"
+kosis {
+    bhan "suru"
+    bhan nabhayeko
+    bhan "yaha pugdaina"
+} samat e {
+    bhan "samatyo: " + e
+}
+"#).unwrap();
+    assert_eq!(output, "suru\nsamatyo: Undefined variable: nabhayeko\n");
+}
+
+#[test]
+fn test_kosis_without_error_skips_samat() {
+    let output = run(r#"
+kosis {
+    bhan "thik"
+} samat e {
+    bhan "samatyo"
+}
+"#).unwrap();
+    assert_eq!(output, "thik\n");
+}
+
+#[test]
+fn test_phek_uncaught() {
+    let error = run("phek \"kehi galat bhayo\"\nbhan \"pachhi\"").unwrap_err();
+    assert_eq!(error, "kehi galat bhayo");
+}
+
+#[test]
+fn test_phek_caught() {
+    let output = run(r#"
+kaam jaach(x) {
+    yedi x < 0 bhane { phek "negative: " + x }
+    pathau x
+}
+kosis {
+    jaach(-1)
+} samat e {
+    bhan e
+}
+"#).unwrap();
+    assert_eq!(output, "negative: -1\n");
+}
+
+#[test]
+fn test_closure_counter() {
+    let output = run(r#"
+kaam banau() {
+    maanau n = 0
+    kaam badhau() {
+        n = n + 1
+        pathau n
+    }
+    pathau badhau
+}
+maanau c = banau()
+bhan c()
+bhan c()
+maanau d = banau()
+bhan d()
+"#).unwrap();
+    assert_eq!(output, "1\n2\n1\n");
+}
+
+#[test]
+fn test_nested_kaam_is_local() {
+    let error = run(r#"
+kaam banau() {
+    maanau n = 0
+    kaam badhau() {
+        n = n + 1
+        pathau n
+    }
+    pathau badhau
+}
+maanau c = banau()
+badhau()
+"#).unwrap_err();
+    assert_eq!(error, "Undefined function: badhau");
+}
+
+#[test]
+fn test_recursive_closure() {
+    let output = run(r#"
+kaam bahira() {
+    kaam fact(n) {
+        yedi n <= 1 bhane { pathau 1 }
+        pathau n * fact(n - 1)
+    }
+    pathau fact
+}
+maanau f = bahira()
+bhan f(5)
+"#).unwrap();
+    assert_eq!(output, "120\n");
+}
+
+#[test]
+fn test_copied_closure_shares_state() {
+    let output = run(r#"
+kaam banau() {
+    maanau n = 0
+    kaam badhau() {
+        n = n + 1
+        pathau n
+    }
+    pathau badhau
+}
+maanau c = banau()
+maanau c2 = c
+c()
+bhan c2()
+"#).unwrap();
+    assert_eq!(output, "2\n");
+}
+
+#[test]
+fn test_badha_and_ghata() {
+    let output = run("maanau x = 1\nbadha x\nbadha x\nbhan x\nghata x\nbhan x").unwrap();
+    assert_eq!(output, "3\n2\n");
+}
+
+#[test]
+fn test_badha_needs_defined_number() {
+    assert_eq!(run("badha nabhayeko").unwrap_err(), "Undefined variable: nabhayeko");
+    assert_eq!(run("maanau s = \"a\"\nbadha s").unwrap_err(), "badha s: Number chahincha, String payo");
+    assert_eq!(run("maanau l = []\nghata l").unwrap_err(), "ghata l: Number chahincha, List payo");
+    assert_eq!(run("sthir c = 1\nbadha c").unwrap_err(), "Sthir char badalna sakdaina: c");
+}
+
+#[test]
+fn test_pratyek_with_index() {
+    let output = run(r#"
+pratyek i, phal ma ["aam", "kera", "syau"] {
+    bhan i, phal
+}
+pratyek i, akshar ma "hi" {
+    bhan i, akshar
+}
+pratyek i, n ma shreni(5, 7) {
+    bhan i, n
+}
+"#).unwrap();
+    assert_eq!(output, "0 aam\n1 kera\n2 syau\n0 h\n1 i\n0 5\n1 6\n");
+}
+
+#[test]
+fn test_pratyek_with_key_and_value() {
+    let output = run(r#"
+maanau jamma = 0
+pratyek k, v ma {"a": 1, "b": 2} {
+    yedi k == "b" bhane { jamma = jamma + v * 10 } natra { jamma = jamma + v }
+}
+bhan jamma
+"#).unwrap();
+    assert_eq!(output, "21\n");
+}
+
+#[test]
+fn test_equality_with_null() {
+    let output = run(r#"
+kaam kehi_chaina() { }
+maanau n = kehi_chaina()
+bhan n == kehi_chaina(), n != kehi_chaina()
+bhan n == 0, n == "", n == [], n == galat
+bhan 0 != n, "" != n
+bhan 1 == 1.0, 1 != 1.0
+"#).unwrap();
+    assert_eq!(output, "sahi galat\ngalat galat galat galat\nsahi sahi\nsahi galat\n");
+}
+
+#[test]
+fn test_dohoryau() {
+    let output = run(r#"
+maanau n = 0
+dohoryau 5 { n = n + 1 }
+bhan n
+dohoryau 0 { bhan "kahile pani hoina" }
+dohoryau 10 {
+    n = n + 1
+    yedi n == 7 bhane { rok }
+}
+bhan n
+"#).unwrap();
+    assert_eq!(output, "5\n7\n");
+}
+
+#[test]
+fn test_dohoryau_needs_whole_count() {
+    for (count, shown) in [("-1", "-1"), ("1.5", "1.5"), ("\"3\"", "3")] {
+        let error = run(&format!("dohoryau {} {{ bhan 1 }}", count)).unwrap_err();
+        assert_eq!(error, format!("dohoryau ko count negative nabhayeko purna sankhya hunu parcha, {} payo", shown));
+    }
+}
+
+#[test]
+fn test_hoina_before_comparison() {
+    let output = run(r#"
+bhan hoina 1 == 2
+bhan hoina 1 < 2 ra sahi
+bhan (hoina sahi) == galat
+"#).unwrap();
+    assert_eq!(output, "sahi\ngalat\nsahi\n");
+}
+
+#[test]
+fn test_number_shaped_tokens_never_panic() {
+    for (source, expected) in [("5.", "5"), ("5.e3", "5000"), ("007", "7"), ("1_0.2_5", "10.25"), ("1e-400", "0"), (".5e1", "5")] {
+        assert_eq!(run(&format!("bhan {}", source)).unwrap(), format!("{}\n", expected), "{}", source);
+    }
+    
+    for source in ["1.2.3", "1e1.5", "1e", "2e+", "0x10", "5abc", "1e5e5", "1__0", "1.5."] {
+        assert!(run(&format!("bhan {}", source)).is_err(), "{}", source);
+    }
+    assert_eq!(run("bhan 1.2.3").unwrap_err(), "Lexer error: Galat number format: 1.2.3 at line 1, column 6");
+    assert_eq!(run("bhan 1e400").unwrap_err(), "Number 1e400 dherai thulo cha");
+}
+
+#[test]
+fn test_numeric_index_reads_string_key() {
+    let output = run(r#"
+maanau d = {"5": "panch", "-1": "minus", "2.5": "dui", 7: "saat"}
+bhan d[5], d[-1], d[2.5], d[7]
+"#).unwrap();
+    assert_eq!(output, "panch minus dui saat\n");
+}
+
+#[test]
+fn test_numeric_index_assigns_string_key() {
+    let output = run(r#"
+maanau d = {"5": "panch"}
+d[5] = "PANCH"
+d[9] = "nau"
+bhan d["5"], lambai(d)
+bhan chha_key(d, 9), chha_key(d, "9")
+"#).unwrap();
+    assert_eq!(output, "PANCH 2\nsahi galat\n");
+}
+
+#[test]
+fn test_sthiti_during_nested_call() {
+    let output = run(r#"
+kaam bhitri() { pathau sthiti() }
+kaam bahiri() { pathau bhitri() }
+bhan bahiri()
+bhan sthiti()
+kaam gaunu(n) {
+    yedi n == 0 bhane { pathau sthiti() }
+    pathau gaunu(n - 1)
+}
+bhan gaunu(2)
+"#).unwrap();
+    assert_eq!(output, "[bahiri, bhitri]\n[]\n[gaunu, gaunu, gaunu]\n");
+}
+
+#[test]
+fn test_sthiti_popped_after_error() {
+    let output = run(r#"
+kaam bigrancha() { phek "oops" }
+kaam bolau() { bigrancha() }
+kosis { bolau() } samat e { bhan sthiti(), e }
+"#).unwrap();
+    assert_eq!(output, "[] oops\n");
+}
+
+#[test]
+fn test_lambai_property() {
+    let output = run(r#"
+maanau l = [1, 2, 3]
+maanau s = "नमस्ते"
+maanau d = {"a": 1, "b": 2}
+bhan l.lambai, s.lambai, d.lambai, shreni(0, 4).lambai
+"#).unwrap();
+    assert_eq!(output, "3 6 2 4\n");
+}
+
+#[test]
+fn test_unknown_property() {
+    assert_eq!(run("maanau l = [1]\nbhan l.chaudai").unwrap_err(), "List ma '.chaudai' access garna mildaina");
+    assert_eq!(run("maanau n = 5\nbhan n.lambai").unwrap_err(), "Number ma '.lambai' access garna mildaina");
+    assert_eq!(run("maanau d = {}\nbhan d.kunai").unwrap_err(), "Key 'kunai' not found in dictionary");
+    
+    // A key of the same name wins over the property
+    assert_eq!(run("maanau d = {\"lambai\": 99}\nbhan d.lambai").unwrap(), "99\n");
+    
+    // lambai is a property, not a method
+    assert_eq!(run("maanau l = [1]\nbhan l.lambai()").unwrap_err(), "List ma '.lambai' access garna mildaina");
+}
+
+#[test]
+fn test_hoina_as_operand() {
+    let output = run("maanau a = sahi\nbhan galat == hoina a, [hoina a]").unwrap();
+    assert_eq!(output, "sahi [galat]\n");
+}
+
+#[test]
+fn test_kaam_in_top_level_block_is_global() {
+    let output = run(r#"
+yedi sahi bhane {
+    kaam f() { pathau 1 }
+}
+pratyek i ma [1] {
+    kaam g() { pathau 2 }
+}
+bhan f(), g()
+"#).unwrap();
+    assert_eq!(output, "1 2\n");
+}
+
+#[test]
+fn test_kaam_in_kaam_is_local() {
+    let output = run(r#"
+kaam bahira() {
+    kaam bhitra() { pathau 3 }
+    pathau bhitra()
+}
+bhan bahira()
+"#).unwrap();
+    assert_eq!(output, "3\n");
+    
+    let error = run("kaam bahira() {\n    kaam bhitra() { }\n}\nbahira()\nbhitra()").unwrap_err();
+    assert_eq!(error, "Undefined function: bhitra");
+}
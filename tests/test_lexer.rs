@@ -0,0 +1,167 @@
+use khukuri::lexer::Lexer;
+use khukuri::token::{Token, TokenType};
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    Lexer::new(source.to_string()).tokenize()
+}
+
+// Text of the single number token `source` lexes to
+fn number(source: &str) -> String {
+    let tokens = tokenize(source).unwrap();
+    assert_eq!(tokens.len(), 2, "{:?}", tokens); // number, EOF
+    assert_eq!(tokens[0].token_type, TokenType::Number);
+    tokens[0].value.clone()
+}
+
+#[test]
+fn test_variable_declaration() {
+    let tokens = tokenize("maanau x = 5").unwrap();
+    
+    assert_eq!(tokens.len(), 5); // maanau, x, =, 5, EOF
+    assert_eq!(tokens[0].token_type, TokenType::Keyword);
+    assert_eq!(tokens[0].value, "maanau");
+    assert_eq!(tokens[4].token_type, TokenType::Eof);
+}
+
+#[test]
+fn test_scientific_notation() {
+    assert_eq!(number("1e3"), "1e3");
+    assert_eq!(number("2.5e-4"), "2.5e-4");
+    assert_eq!(number("1E+2"), "1E+2");
+}
+
+#[test]
+fn test_exponent_without_digits() {
+    assert_eq!(tokenize("1e").unwrap_err(), "Galat number format: 1e at line 1, column 1");
+    assert!(tokenize("maanau x = 2e+").unwrap_err().starts_with("Galat number format: 2e+"));
+}
+
+#[test]
+fn test_digit_separators() {
+    assert_eq!(number("1_000_000"), "1_000_000");
+    assert_eq!(number("3.141_592"), "3.141_592");
+    assert_eq!(number("1_0e1_0"), "1_0e1_0");
+}
+
+#[test]
+fn test_misplaced_digit_separators() {
+    for source in ["_5", "5_", "1__2", "1._5"] {
+        let error = tokenize(source).unwrap_err();
+        assert!(
+            error.contains("'_' digit haru ko bich ma matra aauna sakcha"),
+            "{}: {}", source, error
+        );
+    }
+    assert!(tokenize("_5").unwrap_err().starts_with("Galat number format: _5 "));
+}
+
+#[test]
+fn test_underscore_identifier() {
+    let tokens = tokenize("_x5").unwrap();
+    assert_eq!(tokens[0].token_type, TokenType::Identifier);
+    assert_eq!(tokens[0].value, "_x5");
+}
+
+#[test]
+fn test_tab_width_columns() {
+    let source = "maanau x = 1\n\tbhan @";
+    let error = Lexer::with_tab_width(source.to_string(), 4).tokenize().unwrap_err();
+    assert_eq!(error, "Unexpected character '@' at line 2, column 10");
+    
+    // The default counts a tab as one column
+    assert_eq!(tokenize(source).unwrap_err(), "Unexpected character '@' at line 2, column 7");
+}
+
+#[test]
+fn test_tab_width_rounds_to_next_stop() {
+    let tokens = Lexer::with_tab_width("ab\tx".to_string(), 4).tokenize().unwrap();
+    assert_eq!(tokens[1].value, "x");
+    assert_eq!(tokens[1].column, 5);
+}
+
+#[test]
+fn test_multiple_dots_in_number() {
+    assert_eq!(tokenize("1.2.3").unwrap_err(), "Galat number format: 1.2.3 at line 1, column 1");
+    assert_eq!(tokenize("x = 1e1.5").unwrap_err(), "Galat number format: 1e1.5 at line 1, column 5");
+}
+
+#[test]
+fn test_leading_dot_number() {
+    assert_eq!(number(".5"), ".5");
+    
+    let tokens = tokenize("x.y").unwrap();
+    assert_eq!(tokens[1].token_type, TokenType::Dot);
+}
+
+#[test]
+fn test_columns_after_escaped_string() {
+    let tokens = tokenize(r#"bhan "a\"b\n" == x"#).unwrap();
+    assert_eq!(tokens[1].value, "a\"b\n");
+    assert_eq!(tokens[1].column, 6);
+    assert_eq!(tokens[2].value, "==");
+    assert_eq!(tokens[2].column, 15);
+    assert_eq!(tokens[3].column, 18);
+}
+
+// Text of the single string token `source` lexes to
+fn string(source: &str) -> String {
+    let tokens = tokenize(source).unwrap();
+    assert_eq!(tokens[0].token_type, TokenType::String);
+    tokens[0].value.clone()
+}
+
+#[test]
+fn test_control_escapes() {
+    assert_eq!(string(r#""\0""#), "\0");
+    assert_eq!(string(r#""\a""#), "\x07");
+    assert_eq!(string(r#""\b""#), "\x08");
+    assert_eq!(string(r#""\f""#), "\x0c");
+    assert_eq!(string(r#""\v""#), "\x0b");
+}
+
+#[test]
+fn test_unknown_escape() {
+    assert_eq!(tokenize(r#""\q""#).unwrap_err(), "Unknown escape sequence '\\q' at line 1, column 2");
+}
+
+#[test]
+fn test_tab_width_four() {
+    let tokens = Lexer::with_tab_width("\t\tbhan x".to_string(), 4).tokenize().unwrap();
+    assert_eq!(tokens[0].column, 9);
+    assert_eq!(tokens[1].column, 14);
+    
+    let errors = Lexer::with_tab_width("\tbhan @".to_string(), 4).tokenize_all().unwrap_err();
+    assert_eq!(errors[0].to_string(), "Lexer Error line 1 ma, column 10: Unexpected character '@'");
+}
+
+#[test]
+fn test_semicolon_token() {
+    let tokens = tokenize("maanau x = 1; bhan x").unwrap();
+    assert_eq!(tokens[4].token_type, TokenType::Semicolon);
+    assert_eq!(tokens[4].value, ";");
+    assert_eq!(tokens[4].column, 13);
+    assert_eq!(tokens[5].value, "bhan");
+}
+
+#[test]
+fn test_tokenize_all_collects_invalid_characters() {
+    let errors = Lexer::new("@maanau#x".to_string()).tokenize_all().unwrap_err();
+    let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+    assert_eq!(messages, vec![
+        "Lexer Error line 1 ma, column 1: Unexpected character '@'",
+        "Lexer Error line 1 ma, column 8: Unexpected character '#'",
+    ]);
+    
+    // The normal path still stops at the first one
+    assert_eq!(tokenize("@maanau#x").unwrap_err(), "Unexpected character '@' at line 1, column 1");
+}
+
+#[test]
+fn test_tokenize_all_stops_at_other_errors() {
+    let errors = Lexer::new("@ \"khula".to_string()).tokenize_all().unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert!(errors[1].to_string().contains("Unterminated string"), "{}", errors[1]);
+    
+    let tokens = Lexer::new("maanau x".to_string()).tokenize_all().unwrap();
+    assert_eq!(tokens.len(), 3);
+}
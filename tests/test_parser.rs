@@ -0,0 +1,274 @@
+use khukuri::ast::ASTNode;
+use khukuri::error::CompilerError;
+use khukuri::lexer::Lexer;
+use khukuri::parser::Parser;
+
+fn parse(source: &str) -> Result<ASTNode, String> {
+    let tokens = Lexer::new(source.to_string()).tokenize()?;
+    Parser::new(tokens).parse()
+}
+
+// The statements of a parsed program
+fn statements(source: &str) -> Vec<ASTNode> {
+    match parse(source).unwrap() {
+        ASTNode::Program(statements) => statements,
+        other => panic!("Program expected, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_do_while_loop() {
+    let statements = statements("gara {\n    bhan 1\n} jaba samma galat");
+    assert_eq!(statements.len(), 1);
+    let ASTNode::DoWhileLoop { body, condition } = &statements[0] else {
+        panic!("DoWhileLoop expected, got {:?}", statements[0]);
+    };
+    assert_eq!(body.len(), 1);
+    assert!(matches!(**condition, ASTNode::Boolean(false)));
+}
+
+#[test]
+fn test_do_while_needs_condition() {
+    assert!(parse("gara {\n    bhan 1\n}").is_err());
+}
+
+// The expression printed by a single `bhan <expression>` statement
+fn expression(source: &str) -> ASTNode {
+    match statements(&format!("bhan {}", source)).remove(0) {
+        ASTNode::Print(mut values) => values.remove(0),
+        other => panic!("Print expected, got {:?}", other),
+    }
+}
+
+// The operator at the root of a binary expression
+fn root_operator(node: &ASTNode) -> &str {
+    match node {
+        ASTNode::BinaryOp { operator, .. } => operator,
+        other => panic!("BinaryOp expected, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_bitwise_binds_tighter_than_comparison() {
+    assert_eq!(root_operator(&expression("6 & 3 == 2")), "==");
+    assert_eq!(root_operator(&expression("1 << 2 < 5")), "<");
+}
+
+#[test]
+fn test_shift_binds_looser_than_addition() {
+    let node = expression("1 + 1 << 2");
+    assert_eq!(root_operator(&node), "<<");
+    let ASTNode::BinaryOp { left, .. } = node else { unreachable!() };
+    assert_eq!(root_operator(&left), "+");
+}
+
+#[test]
+fn test_error_column_after_string() {
+    let error = parse(r#"maanau s = "a\tb" == = 1"#).unwrap_err();
+    assert_eq!(error, "Unexpected token Operator '=' in expression at line 1, column 22");
+}
+
+#[test]
+fn test_parse_all_reports_every_error() {
+    let tokens = Lexer::new("maanau x = \nbhan (1 + \nmaanau y = 2\n".to_string()).tokenize().unwrap();
+    let errors = Parser::new(tokens).parse_all().unwrap_err();
+    let lines: Vec<usize> = errors.iter().map(|e| match e {
+        CompilerError::Parser { line, .. } => *line,
+        other => panic!("Parser error expected, got {:?}", other),
+    }).collect();
+    assert_eq!(lines, vec![1, 2]);
+}
+
+#[test]
+fn test_nested_index_assignment() {
+    let statements = statements("grid[0][1] = 5");
+    let ASTNode::IndexAssignment { object, index, value } = &statements[0] else {
+        panic!("IndexAssignment expected, got {:?}", statements[0]);
+    };
+    let ASTNode::IndexAccess { object: target, .. } = &**object else {
+        panic!("IndexAccess expected, got {:?}", object);
+    };
+    assert!(matches!(&**target, ASTNode::Identifier(name) if name == "grid"));
+    assert!(matches!(&**index, ASTNode::Number(n) if n == "1"));
+    assert!(matches!(&**value, ASTNode::Number(n) if n == "5"));
+}
+
+#[test]
+fn test_print_arguments() {
+    for (source, count) in [("bhan", 0), ("bhan 1", 1), ("bhan 1, \"a\"", 2)] {
+        match &statements(source)[0] {
+            ASTNode::Print(values) => assert_eq!(values.len(), count, "{}", source),
+            other => panic!("Print expected, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_list_destructuring() {
+    let statements = statements("maanau [a, b] = l");
+    let ASTNode::Destructuring { names, from_dictionary, is_const, .. } = &statements[0] else {
+        panic!("Destructuring expected, got {:?}", statements[0]);
+    };
+    assert_eq!(names, &["a", "b"]);
+    assert!(!from_dictionary);
+    assert!(!is_const);
+}
+
+#[test]
+fn test_dictionary_destructuring() {
+    let statements = statements("sthir {x, y} = d");
+    let ASTNode::Destructuring { names, from_dictionary, is_const, .. } = &statements[0] else {
+        panic!("Destructuring expected, got {:?}", statements[0]);
+    };
+    assert_eq!(names, &["x", "y"]);
+    assert!(from_dictionary);
+    assert!(is_const);
+}
+
+#[test]
+fn test_chained_comparison() {
+    let error = parse("bhan 1 < x < 10").unwrap_err();
+    assert_eq!(error, "Comparison jodna mildaina: 'a < b < c' ko satta 'a < b ra b < c' lekha at line 1, column 12");
+    
+    assert!(parse("bhan 1 < x ra x < 10").is_ok());
+}
+
+#[test]
+fn test_assignment_in_condition() {
+    let error = parse("jaba samma x = 5 {\n    bhan x\n}").unwrap_err();
+    assert_eq!(error, "Shayad tapai `==` bhanna khojdai hunuhunthyo? at line 1, column 14");
+    
+    let error = parse("yedi x = 5 bhane { bhan x }").unwrap_err();
+    assert_eq!(error, "Shayad tapai `==` bhanna khojdai hunuhunthyo? at line 1, column 8");
+}
+
+#[test]
+fn test_badha_and_ghata_are_step_statements() {
+    let statements = statements("badha x\nghata y");
+    assert_eq!(statements[0], ASTNode::Step { name: "x".to_string(), delta: 1.0 });
+    assert_eq!(statements[1], ASTNode::Step { name: "y".to_string(), delta: -1.0 });
+    
+    assert!(parse("badha 5").is_err());
+}
+
+#[test]
+fn test_semicolon_separated_statements() {
+    let program = statements("maanau x = 1; bhan x;");
+    assert_eq!(program.len(), 2);
+    assert!(matches!(program[0], ASTNode::VarDeclaration { .. }));
+    assert!(matches!(program[1], ASTNode::Print(_)));
+    
+    let ASTNode::WhileLoop { body, .. } = &statements("jaba samma galat { bhan 1; bhan 2 }")[0] else {
+        panic!("WhileLoop expected");
+    };
+    assert_eq!(body.len(), 2);
+}
+
+#[test]
+fn test_duplicate_parameter() {
+    assert_eq!(parse("kaam f(a, a) { pathau a }").unwrap_err(), "Parameter 'a' dui patak aayo at line 1, column 11");
+    assert_eq!(parse("kaam f(a, ...a) { }").unwrap_err(), "Parameter 'a' dui patak aayo at line 1, column 14");
+    
+    let ASTNode::FunctionDeclaration { parameters, .. } = &statements("kaam f(a, b) { pathau a }")[0] else {
+        panic!("FunctionDeclaration expected");
+    };
+    let names: Vec<&str> = parameters.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, vec!["a", "b"]);
+}
+
+#[test]
+fn test_pratyek_index_variable() {
+    let ASTNode::ForEachLoop { index, variable, .. } = &statements("pratyek i, x ma [1] { }")[0] else {
+        panic!("ForEachLoop expected");
+    };
+    assert_eq!(index.as_deref(), Some("i"));
+    assert_eq!(variable, "x");
+    
+    assert_eq!(parse("pratyek x, x ma [1] { }").unwrap_err(), "pratyek ma 'x' dui patak aayo at line 1, column 12");
+}
+
+#[test]
+fn test_repeat_loop() {
+    let ASTNode::RepeatLoop { count, body } = &statements("dohoryau n + 1 {\n    bhan 1\n    bhan 2\n}")[0] else {
+        panic!("RepeatLoop expected");
+    };
+    assert_eq!(root_operator(count), "+");
+    assert_eq!(body.len(), 2);
+    
+    assert!(parse("dohoryau 3").is_err());
+}
+
+#[test]
+fn test_hoina_negates_whole_comparison() {
+    let ASTNode::UnaryOp { operator, operand } = expression("hoina a == b") else {
+        panic!("UnaryOp expected");
+    };
+    assert_eq!(operator, "hoina");
+    assert_eq!(root_operator(&operand), "==");
+    
+    // ...but still binds tighter than ra / wa
+    let node = expression("hoina a ra b");
+    assert_eq!(root_operator(&node), "ra");
+    let ASTNode::BinaryOp { left, .. } = node else { unreachable!() };
+    assert!(matches!(*left, ASTNode::UnaryOp { .. }));
+}
+
+#[test]
+fn test_rok_and_jane_outside_loop() {
+    assert_eq!(parse("bhan 1\nrok").unwrap_err(), "'rok' loop bhitra matra prayog garna milcha at line 2, column 1");
+    assert_eq!(parse("yedi sahi bhane { jane }").unwrap_err(), "'jane' loop bhitra matra prayog garna milcha at line 1, column 19");
+    
+    // A loop around a kaam doesn't make rok valid inside it
+    assert!(parse("pratyek x ma [1] { kaam f() { rok } }").is_err());
+}
+
+#[test]
+fn test_rok_and_jane_inside_loop() {
+    for source in [
+        "jaba samma sahi { yedi sahi bhane { rok } }",
+        "pratyek x ma [1] { jane }",
+        "dohoryau 2 { kosis { rok } samat e { } }",
+        "gara { jane } jaba samma galat",
+        "kaam f() { jaba samma sahi { rok } }",
+    ] {
+        assert!(parse(source).is_ok(), "{}", source);
+    }
+}
+
+#[test]
+fn test_property_access_vs_method_call() {
+    let ASTNode::PropertyAccess { object, property } = expression("suchi.lambai") else {
+        panic!("PropertyAccess expected");
+    };
+    assert!(matches!(*object, ASTNode::Identifier(ref name) if name == "suchi"));
+    assert_eq!(property, "lambai");
+    
+    // A chain only calls the last member
+    let ASTNode::ExpressionCall { callee, .. } = expression("a.b.c()") else {
+        panic!("ExpressionCall expected");
+    };
+    let ASTNode::MemberAccess { object, member } = *callee else {
+        panic!("MemberAccess expected");
+    };
+    assert_eq!(member, "c");
+    assert!(matches!(*object, ASTNode::PropertyAccess { ref property, .. } if property == "b"));
+    
+    let ASTNode::ExpressionCall { callee, arguments } = expression("m.jod(1, 2)") else {
+        panic!("ExpressionCall expected");
+    };
+    assert!(matches!(*callee, ASTNode::MemberAccess { ref member, .. } if member == "jod"));
+    assert_eq!(arguments.len(), 2);
+}
+
+#[test]
+fn test_hoina_as_operand() {
+    let node = expression("galat == hoina a");
+    assert_eq!(root_operator(&node), "==");
+    let ASTNode::BinaryOp { right, .. } = node else { unreachable!() };
+    assert!(matches!(*right, ASTNode::UnaryOp { ref operator, .. } if operator == "hoina"));
+    
+    let node = expression("1 + hoina x");
+    assert_eq!(root_operator(&node), "+");
+    let ASTNode::BinaryOp { right, .. } = node else { unreachable!() };
+    assert!(matches!(*right, ASTNode::UnaryOp { ref operator, .. } if operator == "hoina"));
+}
@@ -0,0 +1,59 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+// Feeds `input` to `khukuri --repl` and returns everything it printed
+fn repl(input: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_khukuri"))
+        .arg("--repl")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    String::from_utf8(output.stdout).unwrap() + &String::from_utf8(output.stderr).unwrap()
+}
+
+#[test]
+fn test_multiline_block() {
+    let output = repl("kaam f(x) {\n    pathau x * 2\n}\nbhan f(4)\n");
+    assert!(output.contains(">> .. .. >> 8\n"), "{}", output);
+}
+
+#[test]
+fn test_unclosed_list_and_call() {
+    let output = repl("bhan [1,\n2]\nbhan lambai([1,\n2, 3])\n");
+    assert!(output.contains(">> .. [1, 2]\n>> .. 3\n"), "{}", output);
+    assert!(!output.contains("error"), "{}", output);
+}
+
+#[test]
+fn test_blank_and_comment_lines() {
+    let output = repl("\n   \n// hi\nbhan 1\n");
+    assert!(output.contains(">> >> >> >> 1\n"), "{}", output);
+    assert!(!output.contains("error"), "{}", output);
+}
+
+#[test]
+fn test_block_after_safe_index() {
+    let output = repl("maanau d = {\"a\": 1}\nyedi d?[\"a\"] bhane {\n    bhan \"cha\"\n}\n");
+    assert!(output.contains(">> >> .. .. cha\n"), "{}", output);
+    assert!(!output.contains("error"), "{}", output);
+}
+
+#[test]
+fn test_vars_after_failed_call() {
+    let output = repl("maanau x = 1\nkaam f() {\n    maanau a = 2\n    pathau nabhayeko\n}\nf()\nmaanau y = 3\n:vars\n");
+    assert!(output.contains("Error bhayo: Undefined variable: nabhayeko"), "{}", output);
+    assert!(output.contains("x = 1\ny = 3\n"), "{}", output);
+    assert!(!output.contains("a = 2"), "{}", output);
+}
+
+#[test]
+fn test_clear() {
+    let output = repl("maanau x = 1\n:clear\n:vars\nbhan x\n");
+    assert!(output.contains("Sabai birsiyo"), "{}", output);
+    assert!(!output.contains("x = 1"), "{}", output);
+    assert!(output.contains("Undefined variable: x"), "{}", output);
+}
@@ -0,0 +1,35 @@
+use khukuri::value::{HashableValue, Value};
+
+#[test]
+fn test_nan_is_not_a_dictionary_key() {
+    let error = HashableValue::from_value(&Value::Number(f64::NAN)).unwrap_err();
+    assert_eq!(error, "NaN dictionary key huna sakdaina");
+}
+
+#[test]
+fn test_negative_zero_key_matches_zero() {
+    let negative = HashableValue::from_value(&Value::Number(-0.0)).unwrap();
+    assert_eq!(negative.to_value(), Value::Number(0.0));
+}
+
+#[test]
+fn test_large_whole_numbers_display() {
+    assert_eq!(Value::Number(1e20).to_string(), "100000000000000000000");
+    // 9007199254740993 isn't representable, the nearest f64 is printed
+    assert_eq!(Value::Number(9007199254740993.0).to_string(), "9007199254740992");
+    assert_eq!(Value::Number(-1e20).to_string(), "-100000000000000000000");
+}
+
+#[test]
+fn test_small_numbers_display() {
+    assert_eq!(Value::Number(42.0).to_string(), "42");
+    assert_eq!(Value::Number(-7.0).to_string(), "-7");
+    assert_eq!(Value::Number(1.5).to_string(), "1.5");
+}
+
+#[test]
+fn test_rounded_leaves_whole_numbers_and_strings() {
+    assert_eq!(Value::Number(1.0 / 3.0).rounded(3), Value::Number(0.333));
+    assert_eq!(Value::Number(12.0).rounded(0), Value::Number(12.0));
+    assert_eq!(Value::String("0.123456".to_string()).rounded(2), Value::String("0.123456".to_string()));
+}